@@ -20,12 +20,19 @@ async function makeProvider(rpcUrl: string, wallet: Keypair) {
 
 async function derivePdas(
   programId: PublicKey,
+  creator: PublicKey,
   beneficiary: PublicKey,
   mint: PublicKey,
-  name: string
+  id: string
 ): Promise<{ vesting: PublicKey; vault: PublicKey; bumpVault: number }> {
   const [vesting, bumpVesting] = PublicKey.findProgramAddressSync(
-    [Buffer.from("vesting"), beneficiary.toBuffer(), mint.toBuffer(), Buffer.from(name)],
+    [
+      Buffer.from("vesting"),
+      creator.toBuffer(),
+      beneficiary.toBuffer(),
+      mint.toBuffer(),
+      new BN(id).toArrayLike(Buffer, "le", 8),
+    ],
     programId
   );
   const [vault, bumpVault] = PublicKey.findProgramAddressSync(
@@ -71,6 +78,7 @@ async function main() {
     .option("--cliff-percentage <number>", "0-100 percent unlocked at start", "0")
     .option("--payment-interval <number>", "in seconds;", "1")
     .requiredOption("--name <string>", "human-readable label")
+    .requiredOption("--id <number>", "schedule ID, unique per creator, beneficiary and mint")
     .option("--revocable", "allow creator to revoke unvested tokens", false)
     .action(async (opts, cmd) => {
       const p: any = cmd.parent; // grab from preAction hook
@@ -93,7 +101,7 @@ async function main() {
       const endTimestamp = Math.floor(endTime / 1000);
 
       // derive PDAs
-      const { vesting, vault, bumpVault } = await derivePdas(programId, beneficiary, mint, opts.name);
+      const { vesting, vault, bumpVault } = await derivePdas(programId, payer, beneficiary, mint, opts.id);
 
       // find the source token account of the payer
       const sourceTokenAccount = await anchor.utils.token.associatedAddress({
@@ -105,12 +113,14 @@ async function main() {
       console.log("Beneficiary token account:", beneficiary.toBase58());
       console.log("Mint:", mint.toBase58());
       console.log("Vesting name:", opts.name);
+      console.log("Vesting ID:", opts.id);
       console.log("Vesting Account:", vesting.toBase58());
       if (opts.paymentInterval == 0) {
         opts.paymentInterval = 1;
       }
       const tx = await anchorProgram.methods
         .initialize(
+          new BN(opts.id),
           new BN(opts.amount),
           new BN(startTimestamp),
           new BN(endTimestamp),
@@ -155,7 +165,8 @@ async function main() {
     .description("Claim your vested tokens")
     // .requiredOption("--beneficiary-token-account <string>", "your token account for the mint")
     .requiredOption("--mint <string>", "token mint public key")
-    .requiredOption("--name <string>", "human-readable label")
+    .requiredOption("--creator <string>", "creator public key")
+    .requiredOption("--id <number>", "schedule ID")
     .action(async (opts, cmd) => {
       const p: any = cmd.parent;
       const anchorProgram: anchor.Program = p.anchorProgram;
@@ -164,7 +175,13 @@ async function main() {
       const mint = new PublicKey(opts.mint);
 
       // derive PDAs
-      const { vesting, vault, bumpVault } = await derivePdas(programId, beneficiary, mint, opts.name);
+      const { vesting, vault, bumpVault } = await derivePdas(
+        programId,
+        new PublicKey(opts.creator),
+        beneficiary,
+        mint,
+        opts.id
+      );
 
       // const beneficiaryTokenAccount = new PublicKey(opts.beneficiaryTokenAccount);
 
@@ -206,7 +223,8 @@ async function main() {
     .description("Get the vasting account address")
     .requiredOption("--beneficiary <string>", "beneficiary public key")
     .requiredOption("--mint <string>", "token mint public key")
-    .requiredOption("--name <string>", "human-readable label")
+    .requiredOption("--creator <string>", "creator public key")
+    .requiredOption("--id <number>", "schedule ID")
     .action(async (opts, cmd) => {
       const p: any = cmd.parent; // grab from preAction hook
       const programId: PublicKey = p.programId;
@@ -214,7 +232,13 @@ async function main() {
       const mint = new PublicKey(opts.mint);
 
       // derive PDAs
-      const { vesting, vault, bumpVault } = await derivePdas(programId, beneficiary, mint, opts.name);
+      const { vesting, vault, bumpVault } = await derivePdas(
+        programId,
+        new PublicKey(opts.creator),
+        beneficiary,
+        mint,
+        opts.id
+      );
       console.log(vesting.toBase58());
       // find the source token account of the payer
     });
@@ -223,7 +247,8 @@ async function main() {
     .description("Print the deserialized vesting account")
     .requiredOption("--beneficiary <string>", "beneficiary public key")
     .requiredOption("--mint <string>", "token mint public key")
-    .requiredOption("--name <string>", "human-readable label")
+    .requiredOption("--creator <string>", "creator public key")
+    .requiredOption("--id <number>", "schedule ID")
     .action(async (opts, cmd) => {
       const p: any = cmd.parent; // grab from preAction hook
       const programId: PublicKey = p.programId;
@@ -232,7 +257,13 @@ async function main() {
       const mint = new PublicKey(opts.mint);
 
       // derive PDAs
-      const { vesting } = await derivePdas(programId, beneficiary, mint, opts.name);
+      const { vesting } = await derivePdas(
+        programId,
+        new PublicKey(opts.creator),
+        beneficiary,
+        mint,
+        opts.id
+      );
 
       // fetch and deserialize the vesting account
       const vestingAccount = await anchorProgram.account["vesting"].fetch(vesting);
@@ -253,7 +284,8 @@ async function main() {
     .description("The current amount of tokens `beneficiary` can claim")
     .requiredOption("--beneficiary <string>", "beneficiary public key")
     .requiredOption("--mint <string>", "token mint public key")
-    .requiredOption("--name <string>", "human-readable label")
+    .requiredOption("--creator <string>", "creator public key")
+    .requiredOption("--id <number>", "schedule ID")
     .action(async (opts, cmd) => {
       const p: any = cmd.parent; // grab from preAction hook
       const programId: PublicKey = p.programId;
@@ -261,7 +293,13 @@ async function main() {
       const mint = new PublicKey(opts.mint);
       const anchorProgram: anchor.Program = p.anchorProgram;
 
-      const { vesting, vault, bumpVault } = await derivePdas(programId, beneficiary, mint, opts.name);
+      const { vesting, vault, bumpVault } = await derivePdas(
+        programId,
+        new PublicKey(opts.creator),
+        beneficiary,
+        mint,
+        opts.id
+      );
 
       const claimable = await anchorProgram.methods
         .estimate()
@@ -0,0 +1,166 @@
+//! Append-only record of the progress of a bulk run
+
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, ErrorKind, Write},
+    path::Path,
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// Signed and about to be sent, may or may not have landed
+    Submitted,
+    /// The vesting account exists on chain
+    Confirmed,
+}
+
+/// One line of the journal
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub row: u64,
+    pub vesting: String,
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// The latest entry of every row, backed by a JSON lines file that is
+/// flushed to disk before each transaction is sent, so a crash can at worst
+/// leave a row `Submitted`
+pub struct Journal {
+    file: Option<File>,
+    entries: HashMap<u64, Entry>,
+}
+
+impl Journal {
+    /// Opens the journal at `path`, creating it if needed
+    pub fn open(path: &Path) -> Result<Self> {
+        let entries = match File::open(path) {
+            Ok(file) => read_entries(BufReader::new(file))
+                .with_context(|| format!("failed to read the journal {}", path.display()))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e).context(format!("failed to open {}", path.display())),
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        Ok(Self {
+            file: Some(file),
+            entries,
+        })
+    }
+
+    /// A journal that reads nothing and writes nothing, for dry runs
+    pub fn disabled() -> Self {
+        Self {
+            file: None,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, row: u64) -> Option<&Entry> {
+        self.entries.get(&row)
+    }
+
+    /// Fails if the journal was written for a different CSV or ID offset,
+    /// i.e. a row it knows derives to another vesting address
+    pub fn check_row(&self, row: u64, vesting: &str) -> Result<()> {
+        match self.entries.get(&row) {
+            Some(entry) if entry.vesting != vesting => bail!(
+                "row {row} was journaled as {} but derives to {vesting}; \
+                 was the CSV or the ID offset changed?",
+                entry.vesting
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn record(&mut self, entry: Entry) -> Result<()> {
+        if let Some(file) = &mut self.file {
+            let mut line = serde_json::to_string(&entry)?;
+            line.push('\n');
+            file.write_all(line.as_bytes())?;
+            file.sync_data()?;
+        }
+        self.entries.insert(entry.row, entry);
+        Ok(())
+    }
+}
+
+fn read_entries(reader: impl BufRead) -> Result<HashMap<u64, Entry>> {
+    let mut entries = HashMap::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: Entry = match serde_json::from_str(&line) {
+            Ok(entry) => entry,
+            // A crash mid-write can only truncate the last line
+            Err(e) if e.is_eof() => break,
+            Err(e) => return Err(e).context(format!("invalid entry on line {}", index + 1)),
+        };
+        entries.insert(entry.row, entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(row: u64, status: Status) -> Entry {
+        Entry {
+            row,
+            vesting: format!("vesting-{row}"),
+            status,
+            signature: Some(format!("signature-{row}")),
+        }
+    }
+
+    #[test]
+    fn test_journal_keeps_latest_entry_per_row() {
+        let path = std::env::temp_dir().join(format!("bulk-journal-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut journal = Journal::open(&path).unwrap();
+        journal.record(entry(1, Status::Submitted)).unwrap();
+        journal.record(entry(2, Status::Submitted)).unwrap();
+        journal.record(entry(1, Status::Confirmed)).unwrap();
+        drop(journal);
+
+        let journal = Journal::open(&path).unwrap();
+        assert_eq!(journal.get(1), Some(&entry(1, Status::Confirmed)));
+        assert_eq!(journal.get(2), Some(&entry(2, Status::Submitted)));
+        assert_eq!(journal.get(3), None);
+        assert!(journal.check_row(1, "vesting-1").is_ok());
+        assert!(journal.check_row(1, "vesting-2").is_err());
+        assert!(journal.check_row(3, "anything").is_ok());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_entries_ignores_truncated_last_line() {
+        let journal = concat!(
+            r#"{"row":1,"vesting":"vesting-1","status":"submitted","signature":"signature-1"}"#,
+            "\n",
+            r#"{"row":1,"vesting":"vesting-1","status":"confi"#,
+        );
+        let entries = read_entries(journal.as_bytes()).unwrap();
+        assert_eq!(entries[&1], entry(1, Status::Submitted));
+    }
+
+    #[test]
+    fn test_read_entries_rejects_corrupt_lines() {
+        let journal = "{\"row\":1}\n{\"row\":2,\"vesting\":\"v\",\"status\":\"confirmed\"}\n";
+        assert!(read_entries(journal.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! `bulk`: create the vesting schedules of a grants CSV
+//!
+//! Every row gets the schedule ID `--id-offset + row`, so each grant has a
+//! fixed vesting address and the program refuses to create it twice. Progress
+//! is journaled next to the CSV; a rerun skips the rows the journal or the
+//! chain knows about and sends the rest.
+
+mod journal;
+pub mod rows;
+
+use std::{
+    collections::{hash_map, HashMap},
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, ensure, Context as _, Result};
+use clap::Args;
+use serde_json::json;
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, pubkey::Pubkey};
+use vesting_client::{
+    accounts::{decode_vesting, fetch_config},
+    instructions::{self, InitializeAccounts},
+    pda,
+    program::{IndexKind, Vesting},
+    PROGRAM_ID,
+};
+
+use crate::{commands::error_message, context::Context, units::format_amount};
+use journal::{Entry, Journal, Status};
+use rows::{parse_grants, Grant};
+
+#[derive(Args)]
+pub struct BulkArgs {
+    /// Grants CSV with the columns `beneficiary, amount, start, cliff, end,
+    /// interval, name, revocable`
+    #[arg(long)]
+    pub csv: PathBuf,
+    /// Mint of the vested token
+    #[arg(long)]
+    pub mint: Pubkey,
+    /// Progress journal, defaults to the CSV path with a `.journal.jsonl` extension
+    #[arg(long)]
+    pub journal: Option<PathBuf>,
+    /// Reconciliation report, defaults to the CSV path with a `.report.csv` extension
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+    /// Grants per transaction
+    #[arg(long, default_value_t = 3)]
+    pub batch_size: usize,
+    /// Added to the 1-based row number to get each schedule's ID
+    #[arg(long, default_value_t = 0)]
+    pub id_offset: u64,
+    /// Compute units requested per grant
+    #[arg(long, default_value_t = 80_000)]
+    pub compute_units: u32,
+}
+
+/// A grant with the addresses it creates
+struct Planned {
+    grant: Grant,
+    vesting: Pubkey,
+}
+
+pub fn bulk(ctx: &Context, args: BulkArgs) -> Result<()> {
+    ensure!(args.batch_size > 0, "--batch-size must be at least 1");
+    let mint = ctx.mint(&args.mint)?;
+    let file =
+        File::open(&args.csv).with_context(|| format!("failed to open {}", args.csv.display()))?;
+    let grants = parse_grants(file, mint.decimals, args.id_offset)?;
+
+    let authority = ctx.wallet_pubkey()?;
+    let template =
+        ctx.initialize_accounts(grants[0].beneficiary, &args.mint, mint.token_program)?;
+    let planned: Vec<Planned> = grants
+        .into_iter()
+        .map(|grant| Planned {
+            vesting: pda::vesting_address(
+                &authority,
+                &grant.beneficiary,
+                &args.mint,
+                grant.params.id,
+            )
+            .0,
+            grant,
+        })
+        .collect();
+
+    let mut journal = if ctx.dry_run() {
+        Journal::disabled()
+    } else {
+        Journal::open(
+            &args
+                .journal
+                .unwrap_or_else(|| args.csv.with_extension("journal.jsonl")),
+        )?
+    };
+    for plan in &planned {
+        journal.check_row(plan.grant.row, &plan.vesting.to_string())?;
+    }
+
+    // A crash between sending and journaling leaves rows `Submitted` that
+    // may have landed; the chain settles them
+    let on_chain = fetch_schedules(ctx, &planned)?;
+    let mut pending = Vec::new();
+    for (plan, vesting) in planned.iter().zip(&on_chain) {
+        let entry = journal.get(plan.grant.row).cloned();
+        let confirmed = entry
+            .as_ref()
+            .is_some_and(|e| e.status == Status::Confirmed);
+        if confirmed {
+            continue;
+        }
+        if vesting.is_some() {
+            journal.record(Entry {
+                row: plan.grant.row,
+                vesting: plan.vesting.to_string(),
+                status: Status::Confirmed,
+                signature: entry.and_then(|e| e.signature),
+            })?;
+        } else {
+            pending.push(plan);
+        }
+    }
+
+    // The token fee comes out of the same account, on top of each grant,
+    // unless the wallet has a fee waiver
+    let config = fetch_config(&ctx.rpc).context("failed to fetch the program config")?;
+    let waiver = ctx
+        .rpc
+        .get_account_with_commitment(&pda::fee_waiver_address(&authority).0, ctx.rpc.commitment())?
+        .value;
+    let waived = waiver.is_some_and(|account| account.owner == PROGRAM_ID);
+    let (mut amount, mut fees) = (0u64, 0u64);
+    for plan in &pending {
+        let grant = plan.grant.params.amount;
+        let fee = if waived {
+            0
+        } else {
+            config
+                .token_fee(grant)
+                .map_err(|e| anyhow!("row {}: {}", plan.grant.row, error_message(e)))?
+        };
+        amount = amount
+            .checked_add(grant)
+            .ok_or_else(|| anyhow!("the pending grants add up to more than u64::MAX"))?;
+        fees = fees
+            .checked_add(fee)
+            .ok_or_else(|| anyhow!("the pending fees add up to more than u64::MAX"))?;
+    }
+    let required = amount
+        .checked_add(fees)
+        .ok_or_else(|| anyhow!("the pending grants and fees add up to more than u64::MAX"))?;
+    let balance: u64 = ctx
+        .rpc
+        .get_token_account_balance(&template.source_token_account)
+        .context("failed to read the source token account")?
+        .amount
+        .parse()?;
+    if balance < required {
+        bail!(
+            "the source token account holds {} but the pending grants need {} ({} in fees)",
+            format_amount(balance, mint.decimals),
+            format_amount(required, mint.decimals),
+            format_amount(fees, mint.decimals),
+        );
+    }
+
+    // Each grant is appended to the first creator and beneficiary index pages
+    // with room, if the owners created any
+    let mut creator_pages = ctx.index_pages(IndexKind::Creator, &authority)?;
+    let mut beneficiary_pages = HashMap::new();
+    let mut accounts = Vec::with_capacity(pending.len());
+    for plan in &pending {
+        let beneficiary = plan.grant.beneficiary;
+        let pages = match beneficiary_pages.entry(beneficiary) {
+            hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(ctx.index_pages(IndexKind::Beneficiary, &beneficiary)?)
+            }
+        };
+        accounts.push(InitializeAccounts {
+            beneficiary,
+            creator_index: creator_pages.take(),
+            beneficiary_index: pages.take(),
+            ..template.clone()
+        });
+    }
+
+    let mut transactions = Vec::new();
+    for (batch, batch_accounts) in pending
+        .chunks(args.batch_size)
+        .zip(accounts.chunks(args.batch_size))
+    {
+        let rows: Vec<u64> = batch.iter().map(|plan| plan.grant.row).collect();
+        let mut batch_instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+            args.compute_units.saturating_mul(batch.len() as u32),
+        )];
+        batch_instructions.extend(
+            batch.iter().zip(batch_accounts).map(|(plan, accounts)| {
+                instructions::initialize(accounts, plan.grant.params.clone())
+            }),
+        );
+        let transaction = ctx.sign(&batch_instructions)?;
+
+        if ctx.dry_run() {
+            transactions.push(json!({
+                "rows": rows,
+                "transaction": Context::serialize(&transaction)?,
+            }));
+            continue;
+        }
+
+        let signature = transaction.signatures[0].to_string();
+        for plan in batch {
+            journal.record(Entry {
+                row: plan.grant.row,
+                vesting: plan.vesting.to_string(),
+                status: Status::Submitted,
+                signature: Some(signature.clone()),
+            })?;
+        }
+        ctx.rpc
+            .send_and_confirm_transaction(&transaction)
+            .with_context(|| format!("the batch of rows {rows:?} failed, rerun to resume"))?;
+        for plan in batch {
+            journal.record(Entry {
+                row: plan.grant.row,
+                vesting: plan.vesting.to_string(),
+                status: Status::Confirmed,
+                signature: Some(signature.clone()),
+            })?;
+        }
+        eprintln!("rows {rows:?}: {signature}");
+    }
+
+    if ctx.dry_run() {
+        ctx.print(&json!({
+            "grants": planned.len(),
+            "pending": pending.len(),
+            "amount": format_amount(amount, mint.decimals),
+            "fees": format_amount(fees, mint.decimals),
+            "transactions": transactions,
+        }));
+        return Ok(());
+    }
+
+    let created = pending.len();
+    let report_path = args
+        .report
+        .unwrap_or_else(|| args.csv.with_extension("report.csv"));
+    let summary = write_report(
+        ctx,
+        &report_path,
+        &planned,
+        &journal,
+        &authority,
+        &args.mint,
+    )?;
+    ctx.print(&json!({
+        "grants": planned.len(),
+        "created": created,
+        "reconciled": summary.reconciled,
+        "mismatched": summary.mismatched,
+        "missing": summary.missing,
+        "report": report_path.display().to_string(),
+    }));
+    if summary.mismatched + summary.missing > 0 {
+        bail!("some grants don't match the chain, see the report");
+    }
+    Ok(())
+}
+
+/// The schedules that exist at each row's vesting address
+fn fetch_schedules(ctx: &Context, planned: &[Planned]) -> Result<Vec<Option<Vesting>>> {
+    let addresses: Vec<Pubkey> = planned.iter().map(|plan| plan.vesting).collect();
+    let mut schedules = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(100) {
+        for account in ctx.rpc.get_multiple_accounts(chunk)? {
+            schedules.push(match account {
+                Some(account) => Some(decode_vesting(&account.data)?.0),
+                None => None,
+            });
+        }
+    }
+    Ok(schedules)
+}
+
+#[derive(Default)]
+struct Summary {
+    reconciled: usize,
+    mismatched: usize,
+    missing: usize,
+}
+
+/// Compares every row with the schedule at its address and writes one line
+/// per row
+fn write_report(
+    ctx: &Context,
+    path: &Path,
+    planned: &[Planned],
+    journal: &Journal,
+    creator: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Summary> {
+    let on_chain = fetch_schedules(ctx, planned)?;
+    let mut report = csv::Writer::from_path(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    report.write_record([
+        "row",
+        "beneficiary",
+        "id",
+        "amount",
+        "vesting",
+        "vault",
+        "signature",
+        "status",
+    ])?;
+
+    let mut summary = Summary::default();
+    for (plan, vesting) in planned.iter().zip(on_chain) {
+        let status = match vesting {
+            // Closed since, or the journal was copied from another cluster
+            None => {
+                summary.missing += 1;
+                "missing".to_string()
+            }
+            Some(vesting) => match mismatches(&plan.grant, &vesting, creator, mint) {
+                fields if fields.is_empty() => {
+                    summary.reconciled += 1;
+                    "ok".to_string()
+                }
+                fields => {
+                    summary.mismatched += 1;
+                    format!("mismatch: {}", fields.join(" "))
+                }
+            },
+        };
+        let signature = journal
+            .get(plan.grant.row)
+            .and_then(|entry| entry.signature.clone())
+            .unwrap_or_default();
+        report.write_record([
+            plan.grant.row.to_string(),
+            plan.grant.beneficiary.to_string(),
+            plan.grant.params.id.to_string(),
+            plan.grant.params.amount.to_string(),
+            plan.vesting.to_string(),
+            pda::vault_address(&plan.vesting).0.to_string(),
+            signature,
+            status,
+        ])?;
+    }
+    report.flush()?;
+    Ok(summary)
+}
+
+/// Names of the schedule fields that differ from the grant
+fn mismatches(
+    grant: &Grant,
+    vesting: &Vesting,
+    creator: &Pubkey,
+    mint: &Pubkey,
+) -> Vec<&'static str> {
+    let params = &grant.params;
+    [
+        ("beneficiary", vesting.beneficiary == grant.beneficiary),
+        ("creator", vesting.creator == *creator),
+        ("mint", vesting.mint == *mint),
+        ("amount", vesting.total_amount == params.amount),
+        ("start", vesting.start_time == params.start_time),
+        ("end", vesting.end_time == params.end_time),
+        ("cliff", vesting.cliff_percentage == params.cliff_percentage),
+        (
+            "interval",
+            vesting.payment_interval == params.payment_interval.unwrap_or(0),
+        ),
+        ("name", vesting.name == params.name),
+        ("revocable", vesting.revocable == params.revocable),
+    ]
+    .into_iter()
+    .filter(|(_, matches)| !matches)
+    .map(|(field, _)| field)
+    .collect()
+}
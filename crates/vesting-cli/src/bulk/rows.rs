@@ -0,0 +1,192 @@
+//! Parsing and validation of the grants CSV
+
+use std::io::Read;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use vesting_client::{instructions::InitializeParams, program::utils};
+
+use crate::commands::error_message;
+use crate::units::{parse_amount, parse_duration, parse_time};
+
+/// A row as exported, before parsing
+#[derive(Deserialize)]
+struct Record {
+    beneficiary: String,
+    amount: String,
+    start: String,
+    cliff: String,
+    end: String,
+    interval: String,
+    name: String,
+    revocable: String,
+}
+
+/// A validated grant
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grant {
+    /// 1-based position in the CSV, the schedule ID is derived from it
+    pub row: u64,
+    pub beneficiary: Pubkey,
+    pub params: InitializeParams,
+}
+
+/// Parses and validates every row, returning all errors at once so a file
+/// can be fixed in one go. Schedule IDs are `id_offset + row`, which keeps
+/// the vesting addresses of a file stable across runs.
+pub fn parse_grants(reader: impl Read, decimals: u8, id_offset: u64) -> Result<Vec<Grant>> {
+    let mut csv = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let mut grants = Vec::new();
+    let mut errors = Vec::new();
+    for (index, record) in csv.deserialize::<Record>().enumerate() {
+        let row = index as u64 + 1;
+        let grant = record
+            .map_err(anyhow::Error::from)
+            .and_then(|record| parse_grant(record, row, decimals, id_offset));
+        match grant {
+            Ok(grant) => grants.push(grant),
+            Err(e) => errors.push(format!("row {row}: {e:#}")),
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!("{} invalid rows\n{}", errors.len(), errors.join("\n"));
+    }
+    if grants.is_empty() {
+        bail!("no grants in the CSV");
+    }
+    Ok(grants)
+}
+
+fn parse_grant(record: Record, row: u64, decimals: u8, id_offset: u64) -> Result<Grant> {
+    let beneficiary = record
+        .beneficiary
+        .parse()
+        .map_err(|_| anyhow!("invalid beneficiary `{}`", record.beneficiary))?;
+    let amount = parse_amount(&record.amount, decimals)?;
+    let start_time = parse_time(&record.start)?;
+    let end_time = parse_time(&record.end)?;
+    let cliff = record.cliff.trim_end_matches('%');
+    let cliff_percentage = if cliff.is_empty() {
+        0
+    } else {
+        cliff
+            .parse()
+            .map_err(|_| anyhow!("invalid cliff `{}`", record.cliff))?
+    };
+    let payment_interval = match record.interval.as_str() {
+        "" => None,
+        interval => Some(parse_duration(interval)?),
+    };
+    let revocable = parse_bool(&record.revocable)?;
+    let id = id_offset
+        .checked_add(row)
+        .context("the ID offset is too large")?;
+
+    utils::validate_schedule(start_time, end_time, cliff_percentage, payment_interval)
+        .map_err(|e| anyhow!("invalid schedule: {}", error_message(e)))?;
+    utils::validate_name(&record.name)
+        .map_err(|e| anyhow!("invalid name: {}", error_message(e)))?;
+
+    Ok(Grant {
+        row,
+        beneficiary,
+        params: InitializeParams {
+            id,
+            amount,
+            start_time,
+            end_time,
+            cliff_percentage,
+            payment_interval,
+            name: record.name,
+            revocable,
+        },
+    })
+}
+
+fn parse_bool(value: &str) -> Result<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" => Ok(true),
+        "false" | "no" | "n" | "0" | "" => Ok(false),
+        _ => bail!("invalid revocable `{value}`, expected true or false"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "beneficiary,amount,start,cliff,end,interval,name,revocable\n";
+
+    fn parse(rows: &str) -> Result<Vec<Grant>> {
+        parse_grants(format!("{HEADER}{rows}").as_bytes(), 6, 100)
+    }
+
+    #[test]
+    fn test_parse_grants() {
+        let beneficiary = Pubkey::new_unique();
+        let grants = parse(&format!(
+            "{beneficiary}, 1.5, 2025-01-01, 25%, 2026-01-01, 30d, Alice, yes\n\
+             {beneficiary},10,1735689600,,1767225600,,Bob,false\n"
+        ))
+        .unwrap();
+
+        assert_eq!(grants.len(), 2);
+        assert_eq!(grants[0].row, 1);
+        assert_eq!(grants[0].beneficiary, beneficiary);
+        assert_eq!(
+            grants[0].params,
+            InitializeParams {
+                id: 101,
+                amount: 1_500_000,
+                start_time: 1_735_689_600,
+                end_time: 1_767_225_600,
+                cliff_percentage: 25,
+                payment_interval: Some(30 * 86_400),
+                name: "Alice".to_string(),
+                revocable: true,
+            }
+        );
+        assert_eq!(grants[1].params.id, 102);
+        assert_eq!(grants[1].params.cliff_percentage, 0);
+        assert_eq!(grants[1].params.payment_interval, None);
+        assert!(!grants[1].params.revocable);
+    }
+
+    #[test]
+    fn test_parse_grants_reports_every_invalid_row() {
+        let beneficiary = Pubkey::new_unique();
+        let error = parse(&format!(
+            "{beneficiary},1,2026-01-01,0,2025-01-01,,Ends before it starts,no\n\
+             {beneficiary},1,2025-01-01,101,2026-01-01,,Cliff too large,no\n\
+             {beneficiary},1,2025-01-01,0,2026-01-01,,A name much longer than thirty-two bytes,no\n\
+             not-a-key,1,2025-01-01,0,2026-01-01,,Bad key,no\n\
+             {beneficiary},1,2025-01-01,0,2026-01-01,,Valid,no\n"
+        ))
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.starts_with("4 invalid rows"), "{error}");
+        for row in 1..=4 {
+            assert!(error.contains(&format!("row {row}:")), "{error}");
+        }
+        assert!(!error.contains("row 5:"), "{error}");
+    }
+
+    #[test]
+    fn test_parse_grants_rejects_empty_file() {
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        assert!(parse_bool("TRUE").unwrap());
+        assert!(!parse_bool("no").unwrap());
+        assert!(!parse_bool("").unwrap());
+        assert!(parse_bool("maybe").is_err());
+    }
+}
@@ -0,0 +1,560 @@
+use std::collections::HashMap;
+
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::{anyhow, bail, Result};
+use clap::{ArgGroup, Args};
+use serde_json::{json, Value};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::pubkey::Pubkey;
+use vesting_client::{
+    accounts::{decode_vesting, fetch_vesting},
+    instructions::{self, InitializeParams},
+    pda,
+    program::{utils, IndexKind, Reconciliation, Vesting, VestingStatus},
+    transaction::simulate_view,
+    PROGRAM_ID,
+};
+
+use crate::context::Context;
+use crate::units::{format_amount, format_time, parse_amount, parse_duration, parse_time};
+use crate::ScheduleArgs;
+
+/// The message of a program error, without the Anchor error's source location
+pub fn error_message(error: anchor_lang::error::Error) -> String {
+    match error {
+        anchor_lang::error::Error::AnchorError(error) => error.error_msg,
+        error => error.to_string(),
+    }
+}
+
+/// Offset of `Vesting::beneficiary`, after the discriminator
+const BENEFICIARY_OFFSET: usize = 8;
+/// Offset of `Vesting::creator`
+const CREATOR_OFFSET: usize = 40;
+
+#[derive(Args)]
+pub struct InitArgs {
+    /// Who receives the tokens
+    #[arg(long)]
+    beneficiary: Pubkey,
+    /// Mint of the vested token
+    #[arg(long)]
+    mint: Pubkey,
+    /// Amount to vest in tokens, e.g. `1500.25`
+    #[arg(long)]
+    amount: String,
+    /// When vesting starts, e.g. `2025-01-31`, `2025-01-31T12:00:00Z` or a unix timestamp
+    #[arg(long)]
+    start: String,
+    /// When everything has vested
+    #[arg(long)]
+    end: String,
+    /// Percentage unlocked at the start
+    #[arg(long, default_value_t = 0)]
+    cliff_percentage: u8,
+    /// Time between unlocks, e.g. `30d`; vests linearly if omitted
+    #[arg(long)]
+    payment_interval: Option<String>,
+    /// Human-readable label
+    #[arg(long)]
+    name: String,
+    /// Schedule ID, unique per creator, beneficiary and mint
+    #[arg(long)]
+    id: u64,
+    /// Let the creator revoke unvested tokens
+    #[arg(long)]
+    revocable: bool,
+}
+
+#[derive(Args)]
+pub struct RevokeArgs {
+    #[command(flatten)]
+    schedule: ScheduleArgs,
+    /// Token account receiving the unclaimed tokens, defaults to the creator's associated token account
+    #[arg(long)]
+    recipient: Option<Pubkey>,
+}
+
+#[derive(Args)]
+pub struct SweepArgs {
+    #[command(flatten)]
+    schedule: ScheduleArgs,
+    /// Token account receiving the surplus, defaults to the creator's associated token account
+    #[arg(long)]
+    recipient: Option<Pubkey>,
+}
+
+#[derive(Args)]
+pub struct RescueArgs {
+    #[command(flatten)]
+    schedule: ScheduleArgs,
+    /// Mint of the tokens sent to the schedule by mistake
+    #[arg(long)]
+    token_mint: Pubkey,
+    /// Token account holding them, defaults to the schedule's associated token account
+    #[arg(long)]
+    token_account: Option<Pubkey>,
+    /// Token account receiving them, defaults to the wallet's associated token account
+    #[arg(long)]
+    destination: Option<Pubkey>,
+}
+
+#[derive(Args)]
+pub struct CloseArgs {
+    #[command(flatten)]
+    schedule: ScheduleArgs,
+    /// Page of the creator's schedule index to remove the schedule from
+    #[arg(long)]
+    creator_index_page: Option<u32>,
+    /// Page of the beneficiary's schedule index to remove the schedule from
+    #[arg(long)]
+    beneficiary_index_page: Option<u32>,
+}
+
+#[derive(Args)]
+pub struct StatusArgs {
+    #[command(flatten)]
+    schedule: ScheduleArgs,
+    /// Date to compute the status at, defaults to now
+    #[arg(long)]
+    at: Option<String>,
+}
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("owner").required(true).args(["creator", "beneficiary"])))]
+pub struct ListArgs {
+    /// List the schedules this key created
+    #[arg(long)]
+    creator: Option<Pubkey>,
+    /// List the schedules this key receives
+    #[arg(long)]
+    beneficiary: Option<Pubkey>,
+    /// Scan all program accounts instead of reading the schedule index, not supported by every RPC
+    #[arg(long)]
+    scan: bool,
+}
+
+/// A schedule's address and the seeds it is derived from
+struct Schedule {
+    creator: Pubkey,
+    beneficiary: Pubkey,
+    mint: Pubkey,
+    vesting: Pubkey,
+}
+
+impl ScheduleArgs {
+    fn resolve(&self, ctx: &Context) -> Result<Schedule> {
+        let creator = match self.creator {
+            Some(creator) => creator,
+            None => ctx.wallet_pubkey()?,
+        };
+        let beneficiary = match self.beneficiary {
+            Some(beneficiary) => beneficiary,
+            None => ctx.wallet_pubkey()?,
+        };
+        // Schedules migrated from before IDs are seeded by their name, not their creator
+        let vesting = match (self.id, &self.name) {
+            (Some(id), _) => pda::vesting_address(&creator, &beneficiary, &self.mint, id).0,
+            (None, Some(name)) => pda::legacy_vesting_address(&beneficiary, &self.mint, name).0,
+            (None, None) => bail!("pass --id or --name"),
+        };
+        Ok(Schedule {
+            creator,
+            beneficiary,
+            mint: self.mint,
+            vesting,
+        })
+    }
+}
+
+pub fn init(ctx: &Context, args: InitArgs) -> Result<()> {
+    let mint = ctx.mint(&args.mint)?;
+    let amount = parse_amount(&args.amount, mint.decimals)?;
+    let start_time = parse_time(&args.start)?;
+    let end_time = parse_time(&args.end)?;
+    let payment_interval = args
+        .payment_interval
+        .as_deref()
+        .map(parse_duration)
+        .transpose()?;
+    utils::validate_schedule(
+        start_time,
+        end_time,
+        args.cliff_percentage,
+        payment_interval,
+    )
+    .map_err(|e| anyhow!("invalid schedule: {}", error_message(e)))?;
+
+    let authority = ctx.wallet_pubkey()?;
+    let accounts = ctx.initialize_accounts(args.beneficiary, &args.mint, mint.token_program)?;
+    let vesting = pda::vesting_address(&authority, &args.beneficiary, &args.mint, args.id).0;
+    let instruction = instructions::initialize(
+        &accounts,
+        InitializeParams {
+            id: args.id,
+            amount,
+            start_time,
+            end_time,
+            cliff_percentage: args.cliff_percentage,
+            payment_interval,
+            name: args.name,
+            revocable: args.revocable,
+        },
+    );
+
+    ctx.submit(
+        &[instruction],
+        json!({
+            "vesting": vesting.to_string(),
+            "vault": pda::vault_address(&vesting).0.to_string(),
+            "amount": format_amount(amount, mint.decimals),
+            "start_time": format_time(start_time),
+            "end_time": format_time(end_time),
+        }),
+    )
+}
+
+pub fn claim(ctx: &Context, args: ScheduleArgs) -> Result<()> {
+    // The wallet claiming is the beneficiary, so it can't stand in for the creator
+    if args.id.is_some() && args.creator.is_none() {
+        bail!("pass --creator, the schedule's address is derived from it");
+    }
+    let schedule = args.resolve(ctx)?;
+    let mint = ctx.mint(&schedule.mint)?;
+    let instruction = instructions::claim(
+        &schedule.beneficiary,
+        &schedule.vesting,
+        &schedule.mint,
+        &mint.token_program,
+    );
+    ctx.submit(
+        &[instruction],
+        json!({ "vesting": schedule.vesting.to_string() }),
+    )
+}
+
+pub fn revoke(ctx: &Context, args: RevokeArgs) -> Result<()> {
+    let schedule = args.schedule.resolve(ctx)?;
+    let mint = ctx.mint(&schedule.mint)?;
+    let recipient = args.recipient.unwrap_or_else(|| {
+        get_associated_token_address_with_program_id(
+            &schedule.creator,
+            &schedule.mint,
+            &mint.token_program,
+        )
+    });
+    let instruction = instructions::revoke(
+        &schedule.creator,
+        &schedule.vesting,
+        &recipient,
+        &schedule.mint,
+        &mint.token_program,
+    );
+    ctx.submit(
+        &[instruction],
+        json!({ "vesting": schedule.vesting.to_string(), "recipient": recipient.to_string() }),
+    )
+}
+
+pub fn sweep_surplus(ctx: &Context, args: SweepArgs) -> Result<()> {
+    let schedule = args.schedule.resolve(ctx)?;
+    let mint = ctx.mint(&schedule.mint)?;
+    let recipient = args.recipient.unwrap_or_else(|| {
+        get_associated_token_address_with_program_id(
+            &schedule.creator,
+            &schedule.mint,
+            &mint.token_program,
+        )
+    });
+    let instruction = instructions::sweep_surplus(
+        &schedule.creator,
+        &schedule.vesting,
+        &recipient,
+        &schedule.mint,
+        &mint.token_program,
+    );
+    ctx.submit(
+        &[instruction],
+        json!({ "vesting": schedule.vesting.to_string(), "recipient": recipient.to_string() }),
+    )
+}
+
+pub fn rescue_tokens(ctx: &Context, args: RescueArgs) -> Result<()> {
+    let schedule = args.schedule.resolve(ctx)?;
+    let authority = ctx.wallet_pubkey()?;
+    let token_program = ctx.mint(&args.token_mint)?.token_program;
+    let token_account = args.token_account.unwrap_or_else(|| {
+        get_associated_token_address_with_program_id(
+            &schedule.vesting,
+            &args.token_mint,
+            &token_program,
+        )
+    });
+    let destination = args.destination.unwrap_or_else(|| {
+        get_associated_token_address_with_program_id(&authority, &args.token_mint, &token_program)
+    });
+    let instruction = instructions::rescue_tokens(
+        &authority,
+        &schedule.vesting,
+        &token_account,
+        &destination,
+        &args.token_mint,
+        &token_program,
+    );
+    ctx.submit(
+        &[instruction],
+        json!({
+            "vesting": schedule.vesting.to_string(),
+            "token_account": token_account.to_string(),
+            "destination": destination.to_string(),
+        }),
+    )
+}
+
+pub fn close(ctx: &Context, args: CloseArgs) -> Result<()> {
+    let schedule = args.schedule.resolve(ctx)?;
+    let mint = ctx.mint(&schedule.mint)?;
+    let creator_index = args
+        .creator_index_page
+        .map(|page| pda::schedule_index_address(IndexKind::Creator, &schedule.creator, page).0);
+    let beneficiary_index = args.beneficiary_index_page.map(|page| {
+        pda::schedule_index_address(IndexKind::Beneficiary, &schedule.beneficiary, page).0
+    });
+    let instruction = instructions::close(
+        &schedule.creator,
+        &schedule.vesting,
+        &mint.token_program,
+        creator_index,
+        beneficiary_index,
+    );
+    ctx.submit(
+        &[instruction],
+        json!({ "vesting": schedule.vesting.to_string() }),
+    )
+}
+
+pub fn address(ctx: &Context, args: ScheduleArgs) -> Result<()> {
+    let schedule = args.resolve(ctx)?;
+    ctx.print(&json!({
+        "vesting": schedule.vesting.to_string(),
+        "vault": pda::vault_address(&schedule.vesting).0.to_string(),
+    }));
+    Ok(())
+}
+
+pub fn account(ctx: &Context, args: ScheduleArgs) -> Result<()> {
+    let schedule = args.resolve(ctx)?;
+    let vesting = fetch_vesting(&ctx.rpc, &schedule.vesting)?;
+    let decimals = ctx.mint(&vesting.mint)?.decimals;
+    ctx.print(&vesting_json(&schedule.vesting, &vesting, decimals));
+    Ok(())
+}
+
+pub fn current(ctx: &Context, args: ScheduleArgs) -> Result<()> {
+    let schedule = args.resolve(ctx)?;
+    let decimals = ctx.mint(&schedule.mint)?.decimals;
+    let fee_payer = view_fee_payer(ctx, &schedule);
+    let claimable: u64 = simulate_view(
+        &ctx.rpc,
+        &fee_payer,
+        instructions::estimate(&fee_payer, &schedule.vesting),
+    )?;
+    ctx.print(&json!({ "claimable": format_amount(claimable, decimals) }));
+    Ok(())
+}
+
+pub fn status(ctx: &Context, args: StatusArgs) -> Result<()> {
+    let schedule = args.schedule.resolve(ctx)?;
+    let decimals = ctx.mint(&schedule.mint)?.decimals;
+    let at = args.at.as_deref().map(parse_time).transpose()?;
+    let status: VestingStatus = simulate_view(
+        &ctx.rpc,
+        &view_fee_payer(ctx, &schedule),
+        instructions::vesting_status(&schedule.vesting, at),
+    )?;
+    ctx.print(&json!({
+        "at": format_time(status.at),
+        "vested": format_amount(status.vested, decimals),
+        "claimed": format_amount(status.claimed, decimals),
+        "claimable": format_amount(status.claimable, decimals),
+        "unvested": format_amount(status.unvested, decimals),
+        "next_unlock_time": status.next_unlock_time.map(format_time),
+        "next_unlock_amount": format_amount(status.next_unlock_amount, decimals),
+        "effective_end_time": format_time(status.effective_end_time),
+    }));
+    Ok(())
+}
+
+pub fn reconcile(ctx: &Context, args: ScheduleArgs) -> Result<()> {
+    let schedule = args.resolve(ctx)?;
+    let decimals = ctx.mint(&schedule.mint)?.decimals;
+    let reconciliation: Reconciliation = simulate_view(
+        &ctx.rpc,
+        &view_fee_payer(ctx, &schedule),
+        instructions::reconcile(&schedule.vesting),
+    )?;
+    ctx.print(&json!({
+        "vault_balance": format_amount(reconciliation.vault_balance, decimals),
+        "owed": format_amount(reconciliation.owed, decimals),
+        "surplus": format_amount(reconciliation.surplus, decimals),
+        "shortfall": format_amount(reconciliation.shortfall, decimals),
+    }));
+    Ok(())
+}
+
+pub fn list(ctx: &Context, args: ListArgs) -> Result<()> {
+    let (kind, owner) = match (args.creator, args.beneficiary) {
+        (Some(creator), None) => (IndexKind::Creator, creator),
+        (None, Some(beneficiary)) => (IndexKind::Beneficiary, beneficiary),
+        _ => bail!("pass exactly one of --creator and --beneficiary"),
+    };
+    let schedules = if args.scan {
+        scan_schedules(ctx, kind, &owner)?
+    } else {
+        indexed_schedules(ctx, kind, &owner)?
+    };
+
+    let mut decimals = HashMap::new();
+    let mut listed = Vec::with_capacity(schedules.len());
+    for (address, vesting) in schedules {
+        let mint_decimals = match decimals.get(&vesting.mint) {
+            Some(d) => *d,
+            None => {
+                let d = ctx.mint(&vesting.mint)?.decimals;
+                decimals.insert(vesting.mint, d);
+                d
+            }
+        };
+        listed.push(vesting_json(&address, &vesting, mint_decimals));
+    }
+    ctx.print(&Value::Array(listed));
+    Ok(())
+}
+
+/// The schedules listed in the owner's index pages, or found by a scan when
+/// the owner has none
+fn indexed_schedules(
+    ctx: &Context,
+    kind: IndexKind,
+    owner: &Pubkey,
+) -> Result<Vec<(Pubkey, Vesting)>> {
+    let pages = ctx.schedule_index_pages(kind, owner)?;
+    if pages.is_empty() {
+        eprintln!("{owner} has no schedule index, scanning program accounts");
+        return scan_schedules(ctx, kind, owner);
+    }
+    let addresses: Vec<Pubkey> = pages
+        .into_iter()
+        .flat_map(|(_, index)| index.schedules)
+        .collect();
+
+    let mut schedules = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(100) {
+        for (address, account) in chunk.iter().zip(ctx.rpc.get_multiple_accounts(chunk)?) {
+            // Closed schedules are removed from the index, unless closed without it
+            if let Some(account) = account {
+                schedules.push((*address, decode_vesting(&account.data)?.0));
+            }
+        }
+    }
+    Ok(schedules)
+}
+
+fn scan_schedules(
+    ctx: &Context,
+    kind: IndexKind,
+    owner: &Pubkey,
+) -> Result<Vec<(Pubkey, Vesting)>> {
+    let offset = match kind {
+        IndexKind::Creator => CREATOR_OFFSET,
+        IndexKind::Beneficiary => BENEFICIARY_OFFSET,
+    };
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, Vesting::DISCRIMINATOR.to_vec())),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, owner.to_bytes().to_vec())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    ctx.rpc
+        .get_program_accounts_with_config(&PROGRAM_ID, config)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, decode_vesting(&account.data)?.0)))
+        .collect()
+}
+
+/// Views are simulated without signatures, any funded account can pay
+fn view_fee_payer(ctx: &Context, schedule: &Schedule) -> Pubkey {
+    ctx.wallet_pubkey().unwrap_or(schedule.creator)
+}
+
+fn vesting_json(address: &Pubkey, vesting: &Vesting, decimals: u8) -> Value {
+    let time_or_null = |time: i64| (time != 0).then(|| format_time(time));
+    json!({
+        "address": address.to_string(),
+        "id": vesting.id,
+        "name": vesting.name,
+        "creator": vesting.creator.to_string(),
+        "beneficiary": vesting.beneficiary.to_string(),
+        "mint": vesting.mint.to_string(),
+        "total_amount": format_amount(vesting.total_amount, decimals),
+        "claimed_amount": format_amount(vesting.claimed_amount, decimals),
+        "accelerated_amount": format_amount(vesting.accelerated_amount, decimals),
+        "start_time": format_time(vesting.start_time),
+        "end_time": format_time(vesting.end_time),
+        "cliff_percentage": vesting.cliff_percentage,
+        "payment_interval": vesting.payment_interval,
+        "revocable": vesting.revocable,
+        "revoked_at": time_or_null(vesting.revoked_at),
+        "paused_at": time_or_null(vesting.paused_at),
+        "paused_duration": vesting.paused_duration,
+        "last_claimed_at": time_or_null(vesting.last_claimed_at),
+        "version": vesting.version,
+        "rescue_policy": format!("{:?}", vesting.rescue_policy),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(id: Option<u64>, name: Option<&str>) -> ScheduleArgs {
+        ScheduleArgs {
+            creator: Some(Pubkey::new_unique()),
+            beneficiary: Some(Pubkey::new_unique()),
+            mint: Pubkey::new_unique(),
+            id,
+            name: name.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_resolve_derives_id_and_legacy_addresses() {
+        let ctx = Context::new(String::new(), None, true, false);
+
+        let by_id = args(Some(7), None);
+        let schedule = by_id.resolve(&ctx).unwrap();
+        let (creator, beneficiary) = (by_id.creator.unwrap(), by_id.beneficiary.unwrap());
+        assert_eq!(
+            schedule.vesting,
+            pda::vesting_address(&creator, &beneficiary, &by_id.mint, 7).0
+        );
+
+        let by_name = args(None, Some("Grant"));
+        let schedule = by_name.resolve(&ctx).unwrap();
+        let beneficiary = by_name.beneficiary.unwrap();
+        assert_eq!(
+            schedule.vesting,
+            pda::legacy_vesting_address(&beneficiary, &by_name.mint, "Grant").0
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use std::{cell::OnceCell, path::PathBuf};
+
+use anchor_lang::AccountDeserialize;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id, token_interface::Mint,
+};
+use anyhow::{anyhow, Context as _, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Map, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+    transaction::Transaction,
+};
+use vesting_client::{
+    accounts::{fetch_config, fetch_schedule_index},
+    instructions::InitializeAccounts,
+    pda,
+    program::{IndexKind, ScheduleIndex, SCHEDULE_INDEX_PAGE_CAPACITY},
+};
+
+/// What commands share: the RPC client, the wallet and the output options
+pub struct Context {
+    pub rpc: RpcClient,
+    keypair_path: PathBuf,
+    wallet: OnceCell<Keypair>,
+    dry_run: bool,
+    json: bool,
+}
+
+/// The parts of a mint the commands need
+pub struct MintInfo {
+    pub decimals: u8,
+    pub token_program: Pubkey,
+}
+
+/// An owner's schedule index pages with room left, filled in page order
+pub struct IndexPages {
+    /// Address and free entries of each page
+    pages: Vec<(Pubkey, usize)>,
+}
+
+impl IndexPages {
+    /// The page to append the next schedule to, if any has room
+    pub fn take(&mut self) -> Option<Pubkey> {
+        let (address, free) = self.pages.iter_mut().find(|(_, free)| *free > 0)?;
+        *free -= 1;
+        Some(*address)
+    }
+}
+
+impl Context {
+    pub fn new(rpc_url: String, keypair: Option<PathBuf>, dry_run: bool, json: bool) -> Self {
+        let keypair_path = keypair.unwrap_or_else(|| {
+            let home = std::env::var("HOME").unwrap_or_default();
+            PathBuf::from(home).join(".config/solana/id.json")
+        });
+        Self {
+            rpc: RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed()),
+            keypair_path,
+            wallet: OnceCell::new(),
+            dry_run,
+            json,
+        }
+    }
+
+    /// The wallet keypair, read on first use
+    pub fn wallet(&self) -> Result<&Keypair> {
+        if let Some(wallet) = self.wallet.get() {
+            return Ok(wallet);
+        }
+        let wallet = read_keypair_file(&self.keypair_path).map_err(|e| {
+            anyhow!(
+                "failed to read keypair {}: {e}",
+                self.keypair_path.display()
+            )
+        })?;
+        Ok(self.wallet.get_or_init(|| wallet))
+    }
+
+    pub fn wallet_pubkey(&self) -> Result<Pubkey> {
+        Ok(self.wallet()?.pubkey())
+    }
+
+    pub fn mint(&self, mint: &Pubkey) -> Result<MintInfo> {
+        let account = self
+            .rpc
+            .get_account(mint)
+            .with_context(|| format!("failed to fetch mint {mint}"))?;
+        let decoded = Mint::try_deserialize(&mut account.data.as_slice())
+            .with_context(|| format!("{mint} is not a mint"))?;
+        Ok(MintInfo {
+            decimals: decoded.decimals,
+            token_program: account.owner,
+        })
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// The owner's schedule index pages, read until the first missing one
+    pub fn schedule_index_pages(
+        &self,
+        kind: IndexKind,
+        owner: &Pubkey,
+    ) -> Result<Vec<(Pubkey, ScheduleIndex)>> {
+        let mut pages = Vec::new();
+        for page in 0.. {
+            let address = pda::schedule_index_address(kind, owner, page).0;
+            if self
+                .rpc
+                .get_account_with_commitment(&address, self.rpc.commitment())?
+                .value
+                .is_none()
+            {
+                break;
+            }
+            pages.push((address, fetch_schedule_index(&self.rpc, &address)?));
+        }
+        Ok(pages)
+    }
+
+    pub fn index_pages(&self, kind: IndexKind, owner: &Pubkey) -> Result<IndexPages> {
+        let pages = self
+            .schedule_index_pages(kind, owner)?
+            .into_iter()
+            .map(|(address, index)| {
+                let free =
+                    (SCHEDULE_INDEX_PAGE_CAPACITY as usize).saturating_sub(index.schedules.len());
+                (address, free)
+            })
+            .collect();
+        Ok(IndexPages { pages })
+    }
+
+    /// `initialize` accounts for the wallet vesting from its associated token
+    /// account, with the treasury accounts the config's fees require and the
+    /// first creator and beneficiary index pages with room
+    pub fn initialize_accounts(
+        &self,
+        beneficiary: Pubkey,
+        mint: &Pubkey,
+        token_program: Pubkey,
+    ) -> Result<InitializeAccounts> {
+        let mut accounts =
+            InitializeAccounts::new(self.wallet_pubkey()?, beneficiary, *mint, token_program);
+        let config = fetch_config(&self.rpc).context("failed to fetch the program config")?;
+        if config.fee_lamports > 0 {
+            accounts.treasury = Some(config.treasury);
+        }
+        if config.fee_bps > 0 {
+            accounts.treasury_token_account = Some(get_associated_token_address_with_program_id(
+                &config.treasury,
+                mint,
+                &token_program,
+            ));
+        }
+        accounts.creator_index = self
+            .index_pages(IndexKind::Creator, &accounts.authority)?
+            .take();
+        accounts.beneficiary_index = self
+            .index_pages(IndexKind::Beneficiary, &beneficiary)?
+            .take();
+        Ok(accounts)
+    }
+
+    /// A transaction of `instructions` paid and signed by the wallet
+    pub fn sign(&self, instructions: &[Instruction]) -> Result<Transaction> {
+        let wallet = self.wallet()?;
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        Ok(Transaction::new_signed_with_payer(
+            instructions,
+            Some(&wallet.pubkey()),
+            &[wallet],
+            blockhash,
+        ))
+    }
+
+    pub fn serialize(transaction: &Transaction) -> Result<String> {
+        Ok(STANDARD.encode(bincode::serialize(transaction)?))
+    }
+
+    /// Signs `instructions` with the wallet and sends them, or prints the
+    /// serialized transaction in dry-run mode. `details` are printed either way.
+    pub fn submit(&self, instructions: &[Instruction], details: Value) -> Result<()> {
+        let transaction = self.sign(instructions)?;
+
+        let mut output = match details {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        };
+        if self.dry_run {
+            output.insert(
+                "transaction".to_string(),
+                json!(Self::serialize(&transaction)?),
+            );
+        } else {
+            let signature = self.rpc.send_and_confirm_transaction(&transaction)?;
+            output.insert("signature".to_string(), json!(signature.to_string()));
+        }
+        self.print(&Value::Object(output));
+        Ok(())
+    }
+
+    /// Prints `value` as JSON, or as indented `key: value` lines
+    pub fn print(&self, value: &Value) {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(value).unwrap());
+        } else {
+            print_human(value, 0);
+        }
+    }
+}
+
+fn print_human(value: &Value, indent: usize) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::Object(_) | Value::Array(_) => {
+                        println!("{pad}{key}:");
+                        print_human(value, indent + 1);
+                    }
+                    _ => println!("{pad}{key}: {}", scalar(value)),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                print_human(item, indent);
+            }
+        }
+        _ => println!("{pad}{}", scalar(value)),
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "-".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_pages_fill_in_order() {
+        let (full, first, second) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut pages = IndexPages {
+            pages: vec![(full, 0), (first, 1), (second, 2)],
+        };
+        assert_eq!(pages.take(), Some(first));
+        assert_eq!(pages.take(), Some(second));
+        assert_eq!(pages.take(), Some(second));
+        assert_eq!(pages.take(), None);
+    }
+}
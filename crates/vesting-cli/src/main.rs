@@ -0,0 +1,120 @@
+//! `vesting-cli`: manage vesting schedules from the command line
+
+mod bulk;
+mod commands;
+mod context;
+mod simulate;
+mod units;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+use context::Context;
+
+#[derive(Parser)]
+#[command(name = "vesting-cli", version, about = "Manage vesting schedules")]
+struct Cli {
+    /// RPC endpoint
+    #[arg(
+        long,
+        global = true,
+        env = "RPC_URL",
+        default_value = "https://api.mainnet-beta.solana.com"
+    )]
+    rpc_url: String,
+
+    /// Wallet keypair file, defaults to ~/.config/solana/id.json
+    #[arg(long, global = true)]
+    keypair: Option<PathBuf>,
+
+    /// Print the signed, serialized transaction instead of sending it
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Print JSON for scripting
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Identifies a schedule by the seeds of its address: its ID, or the name of a
+/// schedule migrated from before IDs
+#[derive(Args)]
+#[command(group(ArgGroup::new("seed").required(true).args(["id", "name"])))]
+pub struct ScheduleArgs {
+    /// Creator of the schedule, defaults to the wallet except for `claim`
+    #[arg(long)]
+    pub creator: Option<Pubkey>,
+    /// Beneficiary of the schedule, defaults to the wallet
+    #[arg(long)]
+    pub beneficiary: Option<Pubkey>,
+    /// Mint of the vested token
+    #[arg(long)]
+    pub mint: Pubkey,
+    /// Schedule ID, unique per creator, beneficiary and mint
+    #[arg(long)]
+    pub id: Option<u64>,
+    /// Name of a schedule created before IDs, whose address is derived from its
+    /// beneficiary, mint and name only
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a vesting schedule funded from the wallet's token account
+    Init(commands::InitArgs),
+    /// Claim the vested tokens, signed by the beneficiary
+    Claim(ScheduleArgs),
+    /// Revoke a schedule, returning the unclaimed tokens to the creator
+    Revoke(commands::RevokeArgs),
+    /// Close a revoked or fully claimed schedule, refunding its rent
+    Close(commands::CloseArgs),
+    /// Return the tokens a vault holds beyond what the schedule owes to the creator
+    SweepSurplus(commands::SweepArgs),
+    /// Move tokens of another mint out of the schedule's token account and close it
+    RescueTokens(commands::RescueArgs),
+    /// Print the address of a schedule
+    Address(ScheduleArgs),
+    /// Print a schedule's account
+    Account(ScheduleArgs),
+    /// Print the amount the beneficiary can claim right now
+    Current(ScheduleArgs),
+    /// Print the vested, claimed and claimable amounts and the next unlock
+    Status(commands::StatusArgs),
+    /// Compare the vault balance with what the schedule still owes
+    Reconcile(ScheduleArgs),
+    /// List the schedules of a creator or beneficiary
+    List(commands::ListArgs),
+    /// Create the schedules of a grants CSV, resuming from its journal
+    Bulk(bulk::BulkArgs),
+    /// Preview the unlocks of a schedule, a grants CSV or a vesting account
+    Simulate(simulate::SimulateArgs),
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let ctx = Context::new(cli.rpc_url, cli.keypair, cli.dry_run, cli.json);
+
+    match cli.command {
+        Command::Init(args) => commands::init(&ctx, args),
+        Command::Claim(args) => commands::claim(&ctx, args),
+        Command::Revoke(args) => commands::revoke(&ctx, args),
+        Command::Close(args) => commands::close(&ctx, args),
+        Command::SweepSurplus(args) => commands::sweep_surplus(&ctx, args),
+        Command::RescueTokens(args) => commands::rescue_tokens(&ctx, args),
+        Command::Address(args) => commands::address(&ctx, args),
+        Command::Account(args) => commands::account(&ctx, args),
+        Command::Current(args) => commands::current(&ctx, args),
+        Command::Status(args) => commands::status(&ctx, args),
+        Command::Reconcile(args) => commands::reconcile(&ctx, args),
+        Command::List(args) => commands::list(&ctx, args),
+        Command::Bulk(args) => bulk::bulk(&ctx, args),
+        Command::Simulate(args) => simulate::simulate(&ctx, args),
+    }
+}
@@ -0,0 +1,573 @@
+//! `simulate`: preview the unlocks of a schedule without sending anything
+//!
+//! The table is computed with the program's own `utils`, so it's exactly what
+//! `claim` would pay out at each point of the grid.
+
+use std::{fs, fs::File, path::PathBuf};
+
+use anyhow::{anyhow, bail, ensure, Context as _, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use clap::{ArgGroup, Args};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use vesting_client::{
+    accounts::{decode_vesting, fetch_vesting},
+    instructions::InitializeParams,
+    program::{utils, Vesting, VESTING_RESERVED_LEN, VESTING_VERSION},
+};
+
+use crate::bulk::rows::parse_grants;
+use crate::commands::error_message;
+use crate::context::Context;
+use crate::units::{format_amount, format_time, parse_amount, parse_duration, parse_time};
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("source").required(true).args(["amount", "csv", "account_file", "address"])))]
+pub struct SimulateArgs {
+    /// Amount to vest in tokens, e.g. `1500.25`
+    #[arg(long, requires_all = ["start", "end"])]
+    amount: Option<String>,
+    /// When vesting starts
+    #[arg(long)]
+    start: Option<String>,
+    /// When everything has vested
+    #[arg(long)]
+    end: Option<String>,
+    /// Percentage unlocked at the start
+    #[arg(long, default_value_t = 0)]
+    cliff_percentage: u8,
+    /// Time between unlocks, e.g. `30d`; vests linearly if omitted
+    #[arg(long)]
+    payment_interval: Option<String>,
+
+    /// Grants CSV in the `bulk` format, every row is simulated
+    #[arg(long)]
+    csv: Option<PathBuf>,
+    /// Vesting account dump, raw (`solana account --output-file`) or JSON
+    /// (`solana account --output json`)
+    #[arg(long)]
+    account_file: Option<PathBuf>,
+    /// Vesting account to fetch
+    #[arg(long)]
+    address: Option<Pubkey>,
+
+    /// Decimals of the token, read from `--mint` or the account's mint if
+    /// omitted. `--amount` and `--csv` need this or `--mint`.
+    #[arg(long)]
+    decimals: Option<u8>,
+    /// Mint to read the decimals from
+    #[arg(long)]
+    mint: Option<Pubkey>,
+
+    /// First point of the grid, defaults to the start time
+    #[arg(long)]
+    from: Option<String>,
+    /// Last point of the grid, defaults to the end time shifted by pauses
+    #[arg(long)]
+    to: Option<String>,
+    /// Time between points, defaults to the payment interval, or a twelfth
+    /// of the duration for linear schedules
+    #[arg(long)]
+    step: Option<String>,
+    /// Write the unlock table to this file, as JSON if it ends in `.json`
+    /// and CSV otherwise
+    #[arg(long)]
+    table: Option<PathBuf>,
+}
+
+/// A schedule to simulate, with its row in the CSV if it came from one
+struct Source {
+    row: Option<u64>,
+    vesting: Vesting,
+}
+
+/// One point of the grid
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Point {
+    pub time: i64,
+    /// Vested at `time` when nothing has been claimed
+    pub cumulative: u64,
+    /// Vested since the previous point
+    pub unlocked: u64,
+    /// What the beneficiary could claim at `time`, given the claims so far
+    pub claimable: u64,
+}
+
+/// Something finance should look at before signing
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Flag {
+    pub code: &'static str,
+    pub detail: String,
+}
+
+pub fn simulate(ctx: &Context, args: SimulateArgs) -> Result<()> {
+    let (sources, decimals) = load_sources(ctx, &args)?;
+
+    let mut schedules = Vec::with_capacity(sources.len());
+    let mut table = Vec::new();
+    for source in &sources {
+        let vesting = &source.vesting;
+        let from = match &args.from {
+            Some(from) => parse_time(from)?,
+            None => vesting.start_time,
+        };
+        let to = match &args.to {
+            Some(to) => parse_time(to)?,
+            None => utils::effective_end_time(vesting, vesting.end_time),
+        };
+        let step = match &args.step {
+            Some(step) => parse_duration(step)?,
+            None => default_step(vesting),
+        };
+        let points = grid(vesting, from, to, step)?;
+        let flags = flags(vesting);
+
+        let total_unlocked = points.iter().map(|point| point.unlocked).sum::<u64>();
+        let first_unlock = utils::next_unlock(vesting, vesting.start_time.saturating_sub(1))
+            .map_err(|e| anyhow!(error_message(e)))?;
+        schedules.push(json!({
+            "row": source.row,
+            "name": vesting.name,
+            "total_amount": format_amount(vesting.total_amount, decimals),
+            "cliff_amount": format_amount(cliff_amount(vesting), decimals),
+            "start_time": format_time(vesting.start_time),
+            "end_time": format_time(vesting.end_time),
+            "payment_interval": vesting.payment_interval,
+            "first_unlock": first_unlock.map(|(time, _)| format_time(time)),
+            "points": points.len(),
+            "unlocked_in_grid": format_amount(total_unlocked, decimals),
+            "vested_at_last_point": points.last().map(|point| format_amount(point.cumulative, decimals)),
+            "flags": flags
+                .iter()
+                .map(|flag| json!({ "code": flag.code, "detail": flag.detail }))
+                .collect::<Vec<_>>(),
+        }));
+        table.extend(
+            points
+                .into_iter()
+                .map(|point| (source.row, vesting.total_amount, point)),
+        );
+    }
+
+    if let Some(path) = &args.table {
+        write_table(path, &table, decimals)?;
+    }
+    ctx.print(&json!({ "schedules": schedules }));
+    Ok(())
+}
+
+/// The schedules to simulate and the decimals of their token
+fn load_sources(ctx: &Context, args: &SimulateArgs) -> Result<(Vec<Source>, u8)> {
+    let account = if let Some(address) = &args.address {
+        Some(fetch_vesting(&ctx.rpc, address)?)
+    } else if let Some(path) = &args.account_file {
+        let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Some(decode_vesting(&account_data(&data)?)?.0)
+    } else {
+        None
+    };
+    if let Some(vesting) = account {
+        let decimals = match args.decimals {
+            Some(decimals) => decimals,
+            None => ctx.mint(&args.mint.unwrap_or(vesting.mint))?.decimals,
+        };
+        return Ok((vec![Source { row: None, vesting }], decimals));
+    }
+
+    // Amounts are given in tokens, so the decimals are needed up front
+    let decimals = match (args.decimals, &args.mint) {
+        (Some(decimals), _) => decimals,
+        (None, Some(mint)) => ctx.mint(mint)?.decimals,
+        (None, None) => bail!("pass --decimals or --mint, amounts are given in tokens"),
+    };
+    let sources = if let Some(path) = &args.csv {
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        parse_grants(file, decimals, 0)?
+            .into_iter()
+            .map(|grant| Source {
+                row: Some(grant.row),
+                vesting: grant_vesting(grant.beneficiary, &grant.params),
+            })
+            .collect()
+    } else {
+        let (Some(amount), Some(start), Some(end)) = (&args.amount, &args.start, &args.end) else {
+            bail!("pass --amount, --start and --end, or --csv, --account-file or --address");
+        };
+        let params = InitializeParams {
+            id: 0,
+            amount: parse_amount(amount, decimals)?,
+            start_time: parse_time(start)?,
+            end_time: parse_time(end)?,
+            cliff_percentage: args.cliff_percentage,
+            payment_interval: args
+                .payment_interval
+                .as_deref()
+                .map(parse_duration)
+                .transpose()?,
+            name: String::new(),
+            revocable: false,
+        };
+        utils::validate_schedule(
+            params.start_time,
+            params.end_time,
+            params.cliff_percentage,
+            params.payment_interval,
+        )
+        .map_err(|e| anyhow!("invalid schedule: {}", error_message(e)))?;
+        vec![Source {
+            row: None,
+            vesting: grant_vesting(Pubkey::default(), &params),
+        }]
+    };
+    Ok((sources, decimals))
+}
+
+/// The account data in a dump, which is either the raw data or the JSON
+/// printed by `solana account --output json` or returned by `getAccountInfo`
+fn account_data(dump: &[u8]) -> Result<Vec<u8>> {
+    let Ok(json) = serde_json::from_slice::<Value>(dump) else {
+        return Ok(dump.to_vec());
+    };
+    let account = json
+        .get("account")
+        .or_else(|| json.get("value"))
+        .unwrap_or(&json);
+    match account.get("data") {
+        Some(Value::Array(data)) if data.get(1) == Some(&json!("base64")) => {
+            let encoded = data[0].as_str().unwrap_or_default();
+            Ok(STANDARD.decode(encoded)?)
+        }
+        _ => bail!("the JSON dump has no base64 `data`, dump it with `--output json`"),
+    }
+}
+
+/// The account `initialize` would write for a grant
+fn grant_vesting(beneficiary: Pubkey, params: &InitializeParams) -> Vesting {
+    Vesting {
+        beneficiary,
+        creator: Pubkey::default(),
+        mint: Pubkey::default(),
+        start_time: params.start_time,
+        end_time: params.end_time,
+        total_amount: params.amount,
+        claimed_amount: 0,
+        cliff_percentage: params.cliff_percentage,
+        payment_interval: params.payment_interval.unwrap_or(0),
+        name: params.name.clone(),
+        revocable: params.revocable,
+        revoked_at: 0,
+        last_claimed_at: 0,
+        bump: 0,
+        paused_at: 0,
+        paused_duration: 0,
+        accelerated_amount: 0,
+        version: VESTING_VERSION,
+        id: Some(params.id),
+        rescue_policy: Default::default(),
+        reserved: [0; VESTING_RESERVED_LEN],
+    }
+}
+
+fn default_step(vesting: &Vesting) -> i64 {
+    if vesting.payment_interval > 0 {
+        vesting.payment_interval
+    } else {
+        (vesting.end_time.saturating_sub(vesting.start_time) / 12).max(1)
+    }
+}
+
+/// Evaluates the schedule every `step` seconds from `from`, and at `to`
+pub fn grid(vesting: &Vesting, from: i64, to: i64, step: i64) -> Result<Vec<Point>> {
+    ensure!(step > 0, "the step must be positive");
+    ensure!(to >= from, "the grid ends before it starts");
+    // Bounds the table of a mistyped step, e.g. `1` over a four-year schedule
+    ensure!(
+        (to - from) / step < 100_000,
+        "the grid has more than 100000 points, use a larger --step"
+    );
+
+    let unclaimed = Vesting {
+        claimed_amount: 0,
+        revoked_at: 0,
+        ..vesting.clone()
+    };
+    let mut points = Vec::new();
+    let mut previous = 0;
+    let mut time = from;
+    loop {
+        let cumulative = utils::calculate_claimable_amount(&unclaimed, time)
+            .map_err(|e| anyhow!(error_message(e)))?;
+        let claimable = utils::calculate_claimable_amount(vesting, time)
+            .map_err(|e| anyhow!(error_message(e)))?;
+        points.push(Point {
+            time,
+            cumulative,
+            unlocked: cumulative - previous,
+            claimable,
+        });
+        previous = cumulative;
+        if time == to {
+            return Ok(points);
+        }
+        time = time.saturating_add(step).min(to);
+    }
+}
+
+fn cliff_amount(vesting: &Vesting) -> u64 {
+    (vesting.total_amount as u128 * vesting.cliff_percentage as u128 / 100) as u64
+}
+
+/// Edge cases of the schedule's rounding and timing, using the same
+/// arithmetic as `utils::calculate_vested_amount`
+pub fn flags(vesting: &Vesting) -> Vec<Flag> {
+    let mut flags = Vec::new();
+    let duration = vesting.end_time.saturating_sub(vesting.start_time);
+    let linear = vesting.total_amount.saturating_sub(cliff_amount(vesting));
+
+    if vesting.payment_interval > 0 && linear > 0 {
+        let intervals = duration / vesting.payment_interval;
+        if intervals == 0 {
+            flags.push(Flag {
+                code: "interval_exceeds_duration",
+                detail: format!(
+                    "the payment interval of {}s is longer than the {}s duration, \
+                     nothing but the cliff unlocks before the end time",
+                    vesting.payment_interval, duration
+                ),
+            });
+        } else {
+            let per_interval = linear / intervals as u64;
+            let dust = linear - per_interval * intervals as u64;
+            if per_interval == 0 {
+                flags.push(Flag {
+                    code: "zero_per_interval",
+                    detail: format!(
+                        "{intervals} intervals share {linear} base units, \
+                         every interval unlocks 0 and everything unlocks at the end time"
+                    ),
+                });
+            } else if dust > 0 {
+                flags.push(Flag {
+                    code: "dust_at_end",
+                    detail: format!(
+                        "{dust} base units don't divide into {intervals} intervals \
+                         and only unlock at the end time"
+                    ),
+                });
+            }
+            let remainder = duration % vesting.payment_interval;
+            if remainder > 0 {
+                flags.push(Flag {
+                    code: "partial_last_interval",
+                    detail: format!(
+                        "the last {remainder}s after the final interval unlock \
+                         nothing until the end time"
+                    ),
+                });
+            }
+        }
+    }
+    if vesting.cliff_percentage == 100 {
+        flags.push(Flag {
+            code: "cliff_unlocks_everything",
+            detail: "the whole amount unlocks at the start time".to_string(),
+        });
+    }
+    if vesting.revoked_at > 0 {
+        flags.push(Flag {
+            code: "revoked",
+            detail: format!(
+                "revoked at {}, nothing is claimable",
+                format_time(vesting.revoked_at)
+            ),
+        });
+    }
+    if vesting.paused_at > 0 {
+        flags.push(Flag {
+            code: "paused",
+            detail: format!(
+                "paused since {}, nothing vests until it is resumed",
+                format_time(vesting.paused_at)
+            ),
+        });
+    }
+    flags
+}
+
+fn write_table(path: &PathBuf, table: &[(Option<u64>, u64, Point)], decimals: u8) -> Result<()> {
+    let percent = |amount: u64, total: u64| match total {
+        0 => "0.00".to_string(),
+        total => format!("{:.2}", amount as f64 * 100.0 / total as f64),
+    };
+
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        let rows: Vec<Value> = table
+            .iter()
+            .map(|(row, total, point)| {
+                json!({
+                    "row": row,
+                    "time": format_time(point.time),
+                    "timestamp": point.time,
+                    "unlocked": format_amount(point.unlocked, decimals),
+                    "cumulative": format_amount(point.cumulative, decimals),
+                    "percent": percent(point.cumulative, *total),
+                    "claimable": format_amount(point.claimable, decimals),
+                })
+            })
+            .collect();
+        fs::write(path, serde_json::to_string_pretty(&rows)?)?;
+        return Ok(());
+    }
+
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    writer.write_record([
+        "row",
+        "time",
+        "timestamp",
+        "unlocked",
+        "cumulative",
+        "percent",
+        "claimable",
+    ])?;
+    for (row, total, point) in table {
+        writer.write_record([
+            row.map(|row| row.to_string()).unwrap_or_default(),
+            format_time(point.time),
+            point.time.to_string(),
+            format_amount(point.unlocked, decimals),
+            format_amount(point.cumulative, decimals),
+            percent(point.cumulative, *total),
+            format_amount(point.claimable, decimals),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(
+        amount: u64,
+        duration: i64,
+        cliff_percentage: u8,
+        interval: Option<i64>,
+    ) -> Vesting {
+        grant_vesting(
+            Pubkey::default(),
+            &InitializeParams {
+                id: 1,
+                amount,
+                start_time: 1_000,
+                end_time: 1_000 + duration,
+                cliff_percentage,
+                payment_interval: interval,
+                name: "Test".to_string(),
+                revocable: false,
+            },
+        )
+    }
+
+    fn codes(vesting: &Vesting) -> Vec<&'static str> {
+        flags(vesting).into_iter().map(|flag| flag.code).collect()
+    }
+
+    #[test]
+    fn test_grid_matches_interval_schedule() {
+        let vesting = schedule(1_000, 400, 20, Some(100));
+        let points = grid(&vesting, 1_000, 1_400, 100).unwrap();
+
+        let cumulative: Vec<u64> = points.iter().map(|point| point.cumulative).collect();
+        assert_eq!(cumulative, [200, 400, 600, 800, 1_000]);
+        let unlocked: Vec<u64> = points.iter().map(|point| point.unlocked).collect();
+        assert_eq!(unlocked, [200, 200, 200, 200, 200]);
+        assert_eq!(
+            points.iter().map(|point| point.unlocked).sum::<u64>(),
+            1_000
+        );
+    }
+
+    #[test]
+    fn test_grid_always_ends_at_to() {
+        let vesting = schedule(1_000, 1_000, 0, None);
+        let points = grid(&vesting, 1_000, 2_000, 300).unwrap();
+
+        let times: Vec<i64> = points.iter().map(|point| point.time).collect();
+        assert_eq!(times, [1_000, 1_300, 1_600, 1_900, 2_000]);
+        assert_eq!(points.last().unwrap().cumulative, 1_000);
+    }
+
+    #[test]
+    fn test_grid_separates_claims_from_vesting() {
+        let mut vesting = schedule(1_000, 1_000, 0, None);
+        vesting.claimed_amount = 300;
+        let points = grid(&vesting, 1_500, 1_500, 1).unwrap();
+
+        assert_eq!(points[0].cumulative, 500);
+        assert_eq!(points[0].claimable, 200);
+    }
+
+    #[test]
+    fn test_grid_rejects_bad_bounds() {
+        let vesting = schedule(1_000, 1_000, 0, None);
+        assert!(grid(&vesting, 1_000, 2_000, 0).is_err());
+        assert!(grid(&vesting, 2_000, 1_000, 1).is_err());
+        assert!(grid(&vesting, 0, i64::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn test_flags() {
+        assert!(codes(&schedule(1_000, 400, 20, Some(100))).is_empty());
+        assert!(codes(&schedule(1_000, 1_000, 0, None)).is_empty());
+        assert_eq!(
+            codes(&schedule(1_000, 300, 0, Some(400))),
+            ["interval_exceeds_duration"]
+        );
+        assert_eq!(codes(&schedule(1_000, 300, 0, Some(100))), ["dust_at_end"]);
+        assert_eq!(
+            codes(&schedule(1_000, 450, 0, Some(100))),
+            ["partial_last_interval"]
+        );
+        assert_eq!(
+            codes(&schedule(3, 400, 0, Some(100))),
+            ["zero_per_interval"]
+        );
+        assert_eq!(
+            codes(&schedule(1_000, 400, 100, Some(1_000))),
+            ["cliff_unlocks_everything"]
+        );
+    }
+
+    #[test]
+    fn test_dust_flag_matches_program() {
+        let vesting = schedule(1_000, 300, 0, Some(100));
+        let points = grid(&vesting, 1_000, 1_300, 100).unwrap();
+        let unlocked: Vec<u64> = points.iter().map(|point| point.unlocked).collect();
+        // 1000 / 3 = 333 per interval, the last unlock carries the 1 base unit of dust
+        assert_eq!(unlocked, [0, 333, 333, 334]);
+    }
+
+    #[test]
+    fn test_account_data() {
+        let raw = vec![1, 2, 3];
+        assert_eq!(account_data(&raw).unwrap(), raw);
+
+        let dump = json!({
+            "pubkey": Pubkey::default().to_string(),
+            "account": { "data": [STANDARD.encode(&raw), "base64"], "lamports": 1 },
+        });
+        assert_eq!(account_data(dump.to_string().as_bytes()).unwrap(), raw);
+
+        let rpc = json!({ "value": { "data": [STANDARD.encode(&raw), "base64"] } });
+        assert_eq!(account_data(rpc.to_string().as_bytes()).unwrap(), raw);
+
+        let parsed = json!({ "account": { "data": { "parsed": {} } } });
+        assert!(account_data(parsed.to_string().as_bytes()).is_err());
+    }
+}
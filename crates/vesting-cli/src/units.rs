@@ -0,0 +1,141 @@
+//! Parsing and formatting of token amounts, dates and durations
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+/// Parses a decimal amount of tokens, e.g. `12.5`, into base units
+pub fn parse_amount(value: &str, decimals: u8) -> Result<u64> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        bail!("invalid amount `{value}`");
+    }
+    if fraction.len() > decimals as usize {
+        bail!("`{value}` has more than {decimals} decimals");
+    }
+    let digits = format!("{whole}{fraction:0<width$}", width = decimals as usize);
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        bail!("invalid amount `{value}`");
+    }
+    digits
+        .parse::<u64>()
+        .with_context(|| format!("`{value}` is too large"))
+}
+
+/// Formats base units as a decimal amount, without trailing zeros
+pub fn format_amount(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let scale = 10u128.pow(decimals as u32);
+    let (whole, fraction) = (amount as u128 / scale, amount as u128 % scale);
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{fraction:0>width$}", width = decimals as usize);
+    format!("{whole}.{}", fraction.trim_end_matches('0'))
+}
+
+/// Parses `now`, a unix timestamp, an RFC 3339 date time or a UTC date
+/// (`2025-01-31`) or date time (`2025-01-31 12:00[:00]`)
+pub fn parse_time(value: &str) -> Result<i64> {
+    if value == "now" {
+        return Ok(Utc::now().timestamp());
+    }
+    if let Ok(timestamp) = value.parse::<i64>() {
+        return Ok(timestamp);
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.timestamp());
+    }
+    for format in [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(time.and_utc().timestamp());
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp());
+    }
+    Err(anyhow!("invalid date `{value}`, expected e.g. `2025-01-31`, `2025-01-31T12:00:00Z` or a unix timestamp"))
+}
+
+/// Formats a unix timestamp as an RFC 3339 UTC date time
+pub fn format_time(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// Parses a duration in seconds, optionally with a `s`, `m`, `h`, `d` or `w` unit
+pub fn parse_duration(value: &str) -> Result<i64> {
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => value.split_at(index),
+        None => (value, "s"),
+    };
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => bail!("invalid duration `{value}`, expected e.g. `3600`, `30m`, `12h` or `7d`"),
+    };
+    let number: i64 = number
+        .parse()
+        .with_context(|| format!("invalid duration `{value}`"))?;
+    number
+        .checked_mul(seconds)
+        .with_context(|| format!("`{value}` is too long"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_decimal_amounts() {
+        assert_eq!(parse_amount("12.5", 6).unwrap(), 12_500_000);
+        assert_eq!(parse_amount("12", 6).unwrap(), 12_000_000);
+        assert_eq!(parse_amount(".000001", 6).unwrap(), 1);
+        assert_eq!(parse_amount("7", 0).unwrap(), 7);
+        assert!(parse_amount("1.0000001", 6).is_err());
+        assert!(parse_amount("1,5", 6).is_err());
+        assert!(parse_amount("-1", 6).is_err());
+        assert!(parse_amount(".", 6).is_err());
+        assert!(parse_amount("18446744073709551616", 0).is_err());
+    }
+
+    #[test]
+    fn formats_amounts_without_trailing_zeros() {
+        assert_eq!(format_amount(12_500_000, 6), "12.5");
+        assert_eq!(format_amount(12_000_000, 6), "12");
+        assert_eq!(format_amount(1, 6), "0.000001");
+        assert_eq!(format_amount(u64::MAX, 9), "18446744073.709551615");
+    }
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(parse_time("1700000000").unwrap(), 1_700_000_000);
+        assert_eq!(parse_time("2024-01-01").unwrap(), 1_704_067_200);
+        assert_eq!(parse_time("2024-01-01 01:00").unwrap(), 1_704_070_800);
+        assert_eq!(
+            parse_time("2024-01-01T00:00:00+01:00").unwrap(),
+            1_704_063_600
+        );
+        assert!(parse_time("01/01/2024").is_err());
+        assert_eq!(format_time(1_704_067_200), "2024-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("90").unwrap(), 90);
+        assert_eq!(parse_duration("30m").unwrap(), 1_800);
+        assert_eq!(parse_duration("7d").unwrap(), 604_800);
+        assert!(parse_duration("7y").is_err());
+        assert!(parse_duration("d").is_err());
+    }
+}
@@ -0,0 +1,41 @@
+//! Fetching and decoding the program's accounts
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use solana_client::rpc_client::RpcClient;
+use solana_vesting_program::{read_vesting, Config, ScheduleIndex, Vesting, ID};
+
+use crate::error::{ClientError, Result};
+use crate::pda;
+
+/// Decodes a vesting account of any layout version, returning whether it
+/// still has to be migrated with `migrate_vesting`
+pub fn decode_vesting(data: &[u8]) -> Result<(Vesting, bool)> {
+    Ok(read_vesting(data)?)
+}
+
+pub fn fetch_vesting(rpc: &RpcClient, address: &Pubkey) -> Result<Vesting> {
+    let data = fetch_data(rpc, address)?;
+    Ok(decode_vesting(&data)?.0)
+}
+
+pub fn fetch_config(rpc: &RpcClient) -> Result<Config> {
+    fetch_account(rpc, &pda::config_address().0)
+}
+
+pub fn fetch_schedule_index(rpc: &RpcClient, address: &Pubkey) -> Result<ScheduleIndex> {
+    fetch_account(rpc, address)
+}
+
+/// Fetches and decodes any account of the program
+pub fn fetch_account<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    let data = fetch_data(rpc, address)?;
+    Ok(T::try_deserialize(&mut data.as_slice())?)
+}
+
+fn fetch_data(rpc: &RpcClient, address: &Pubkey) -> Result<Vec<u8>> {
+    let account = rpc.get_account(address)?;
+    if account.owner != ID {
+        return Err(ClientError::WrongOwner(*address));
+    }
+    Ok(account.data)
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::Pubkey;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error(transparent)]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    #[error(transparent)]
+    Anchor(#[from] anchor_lang::error::Error),
+    #[error("account {0} is not owned by the vesting program")]
+    WrongOwner(Pubkey),
+    #[error("the simulation failed: {0}")]
+    Simulation(String),
+    #[error("the simulation returned no data")]
+    NoReturnData,
+    #[error("invalid return data: {0}")]
+    InvalidReturnData(String),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(Box::new(err))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
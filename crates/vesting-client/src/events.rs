@@ -0,0 +1,167 @@
+//! Decoding the program's events, from `Program data:` log lines or from the
+//! inner instructions of the `event-cpi` self-CPI
+
+use anchor_lang::{event::EVENT_IX_TAG_LE, AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_vesting_program::events::*;
+use solana_vesting_program::ID;
+
+/// Any event emitted by the program
+pub enum VestingEvent {
+    Initialized(VestingInitialized),
+    Claimed(VestingClaimed),
+    Revoked(RevokedEvent),
+    Paused(VestingPaused),
+    Resumed(VestingResumed),
+    Amended(VestingAmended),
+    Accelerated(VestingAccelerated),
+    Closed(VestingClosed),
+    SurplusSwept(SurplusSwept),
+    TokensRescued(TokensRescued),
+}
+
+impl VestingEvent {
+    /// Decodes an event from its discriminator followed by its Borsh data,
+    /// `None` for data of another event or program
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, mut data) = data.split_at(8);
+        let data = &mut data;
+        let event = match discriminator {
+            d if d == VestingInitialized::DISCRIMINATOR => {
+                Self::Initialized(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            d if d == VestingClaimed::DISCRIMINATOR => {
+                Self::Claimed(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            d if d == RevokedEvent::DISCRIMINATOR => {
+                Self::Revoked(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            d if d == VestingPaused::DISCRIMINATOR => {
+                Self::Paused(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            d if d == VestingResumed::DISCRIMINATOR => {
+                Self::Resumed(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            d if d == VestingAmended::DISCRIMINATOR => {
+                Self::Amended(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            d if d == VestingAccelerated::DISCRIMINATOR => {
+                Self::Accelerated(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            d if d == VestingClosed::DISCRIMINATOR => {
+                Self::Closed(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            d if d == SurplusSwept::DISCRIMINATOR => {
+                Self::SurplusSwept(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            d if d == TokensRescued::DISCRIMINATOR => {
+                Self::TokensRescued(AnchorDeserialize::deserialize(data).ok()?)
+            }
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    /// Decodes the data of a self-CPI event instruction
+    pub fn decode_cpi(instruction_data: &[u8]) -> Option<Self> {
+        Self::decode(instruction_data.strip_prefix(&EVENT_IX_TAG_LE)?)
+    }
+}
+
+/// Events the program wrote to the logs of a transaction with `emit!`,
+/// ignoring `Program data:` lines of the programs it invoked or was invoked by
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<VestingEvent> {
+    let program_id = ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(data) = line.strip_prefix("Program data: ") {
+            if stack.last() == Some(&program_id.as_str()) {
+                if let Some(event) = STANDARD
+                    .decode(data)
+                    .ok()
+                    .and_then(|d| VestingEvent::decode(&d))
+                {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(id), Some("invoke")) => stack.push(id),
+                (Some(_), Some("success" | "failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{prelude::Pubkey, Event};
+
+    fn claimed(amount: u64) -> VestingClaimed {
+        VestingClaimed {
+            schema_version: solana_vesting_program::EVENT_SCHEMA_VERSION,
+            vesting: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount,
+            claimed_amount: amount,
+            time: 100,
+        }
+    }
+
+    fn data_line(event: &impl Event) -> String {
+        format!("Program data: {}", STANDARD.encode(event.data()))
+    }
+
+    #[test]
+    fn parses_events_of_the_program_only() {
+        let token_program = anchor_spl::token::ID.to_string();
+        let logs = vec![
+            format!("Program {ID} invoke [1]"),
+            "Program log: Instruction: Claim".to_string(),
+            format!("Program {token_program} invoke [2]"),
+            data_line(&claimed(1)),
+            format!("Program {token_program} success"),
+            data_line(&claimed(2)),
+            format!("Program {ID} consumed 12345 of 200000 compute units"),
+            format!("Program {ID} success"),
+            data_line(&claimed(3)),
+        ];
+
+        let events = parse_logs(&logs);
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            VestingEvent::Claimed(event) => assert_eq!(event.amount, 2),
+            _ => panic!("expected a claim"),
+        }
+    }
+
+    #[test]
+    fn decodes_self_cpi_events() {
+        let event = claimed(5);
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend(event.data());
+
+        match VestingEvent::decode_cpi(&data) {
+            Some(VestingEvent::Claimed(decoded)) => {
+                assert_eq!(decoded.vesting, event.vesting);
+                assert_eq!(decoded.claimed_amount, 5);
+            }
+            _ => panic!("expected a claim"),
+        }
+        assert!(VestingEvent::decode(&data).is_none());
+    }
+}
@@ -0,0 +1,589 @@
+//! Builders for the program's instructions, using its generated account
+//! structs and instruction data so account order and discriminators match
+
+use anchor_lang::{prelude::Pubkey, system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use solana_sdk::instruction::Instruction;
+use solana_vesting_program::{accounts, instruction, Acceleration, IndexKind, RescuePolicy, ID};
+
+use crate::pda;
+
+/// Schedule parameters of `initialize`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InitializeParams {
+    pub id: u64,
+    pub amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub cliff_percentage: u8,
+    /// Seconds between unlocks, `None` for a linear schedule
+    pub payment_interval: Option<i64>,
+    pub name: String,
+    pub revocable: bool,
+}
+
+/// Accounts of `initialize` that can't be derived
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InitializeAccounts {
+    /// Pays the rent of the new accounts and the lamport fee
+    pub payer: Pubkey,
+    /// Owner of `source_token_account`, becomes the creator
+    pub authority: Pubkey,
+    pub source_token_account: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    /// Only needed when the config charges a lamport fee
+    pub treasury: Option<Pubkey>,
+    /// Only needed when the config charges a token fee
+    pub treasury_token_account: Option<Pubkey>,
+    pub creator_index: Option<Pubkey>,
+    pub beneficiary_index: Option<Pubkey>,
+}
+
+impl InitializeAccounts {
+    /// Accounts for `authority` vesting its tokens held in its associated token account
+    pub fn new(
+        authority: Pubkey,
+        beneficiary: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+    ) -> Self {
+        Self {
+            payer: authority,
+            authority,
+            source_token_account: get_associated_token_address_with_program_id(
+                &authority,
+                &mint,
+                &token_program,
+            ),
+            beneficiary,
+            mint,
+            token_program,
+            treasury: None,
+            treasury_token_account: None,
+            creator_index: None,
+            beneficiary_index: None,
+        }
+    }
+}
+
+/// Creates a schedule at `pda::vesting_address(authority, beneficiary, mint, id)`
+pub fn initialize(accounts: &InitializeAccounts, params: InitializeParams) -> Instruction {
+    let vesting = pda::vesting_address(
+        &accounts.authority,
+        &accounts.beneficiary,
+        &accounts.mint,
+        params.id,
+    )
+    .0;
+    let metas = accounts::InitializeVesting {
+        vesting,
+        vault: pda::vault_address(&vesting).0,
+        payer: accounts.payer,
+        authority: accounts.authority,
+        source_token_account: accounts.source_token_account,
+        beneficiary: accounts.beneficiary,
+        mint: accounts.mint,
+        token_program: accounts.token_program,
+        system_program: system_program::ID,
+        config: pda::config_address().0,
+        allowed_mint: pda::allowed_mint_address(&accounts.mint).0,
+        treasury: accounts.treasury,
+        treasury_token_account: accounts.treasury_token_account,
+        fee_waiver: pda::fee_waiver_address(&accounts.authority).0,
+        creator_index: accounts.creator_index,
+        beneficiary_index: accounts.beneficiary_index,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority_address().0,
+        #[cfg(feature = "event-cpi")]
+        program: ID,
+    }
+    .to_account_metas(None);
+    let data = instruction::Initialize {
+        id: params.id,
+        amount: params.amount,
+        start_time: params.start_time,
+        end_time: params.end_time,
+        cliff_percentage: params.cliff_percentage,
+        payment_interval: params.payment_interval,
+        name: params.name,
+        revocable: params.revocable,
+    }
+    .data();
+    Instruction::new_with_bytes(ID, &data, metas)
+}
+
+/// Claims everything vested so far into the beneficiary's associated token
+/// account, which is created if needed
+pub fn claim(
+    beneficiary: &Pubkey,
+    vesting: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let metas = accounts::Claim {
+        vesting: *vesting,
+        vault: pda::vault_address(vesting).0,
+        beneficiary_token_account: get_associated_token_address_with_program_id(
+            beneficiary,
+            mint,
+            token_program,
+        ),
+        token_program: *token_program,
+        mint: *mint,
+        beneficiary: *beneficiary,
+        system_program: system_program::ID,
+        associated_token_program: associated_token::ID,
+        config: pda::config_address().0,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority_address().0,
+        #[cfg(feature = "event-cpi")]
+        program: ID,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::Claim {}.data(), metas)
+}
+
+/// Revokes the schedule, returning the unclaimed tokens to `recipient_account`
+pub fn revoke(
+    creator: &Pubkey,
+    vesting: &Pubkey,
+    recipient_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let metas = accounts::Revoke {
+        vesting: *vesting,
+        vault: pda::vault_address(vesting).0,
+        recipient_account: *recipient_account,
+        creator: *creator,
+        mint: *mint,
+        token_program: *token_program,
+        config: pda::config_address().0,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority_address().0,
+        #[cfg(feature = "event-cpi")]
+        program: ID,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::Revoke {}.data(), metas)
+}
+
+/// View returning the claimable amount as `u64`, to be simulated with
+/// `transaction::simulate_view`
+pub fn estimate(signer: &Pubkey, vesting: &Pubkey) -> Instruction {
+    let metas = accounts::ReadOnlyClaim {
+        vesting: *vesting,
+        signer: *signer,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::Estimate {}.data(), metas)
+}
+
+/// Stops the schedule from vesting until `resume`
+pub fn pause(creator: &Pubkey, vesting: &Pubkey) -> Instruction {
+    let metas = accounts::Pause {
+        vesting: *vesting,
+        creator: *creator,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::Pause {}.data(), metas)
+}
+
+/// Resumes a paused schedule, shifting it by the time spent paused
+pub fn resume(creator: &Pubkey, vesting: &Pubkey) -> Instruction {
+    let metas = accounts::Resume {
+        vesting: *vesting,
+        creator: *creator,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::Resume {}.data(), metas)
+}
+
+pub fn accelerate(creator: &Pubkey, vesting: &Pubkey, acceleration: Acceleration) -> Instruction {
+    let metas = accounts::Accelerate {
+        vesting: *vesting,
+        creator: *creator,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::Accelerate { acceleration }.data(), metas)
+}
+
+/// Replaces the schedule's terms, signed by both parties
+pub fn amend_schedule(
+    creator: &Pubkey,
+    beneficiary: &Pubkey,
+    vesting: &Pubkey,
+    end_time: i64,
+    cliff_percentage: u8,
+    payment_interval: Option<i64>,
+    revocable: bool,
+) -> Instruction {
+    let metas = accounts::AmendSchedule {
+        vesting: *vesting,
+        creator: *creator,
+        beneficiary: *beneficiary,
+    }
+    .to_account_metas(None);
+    let data = instruction::AmendSchedule {
+        end_time,
+        cliff_percentage,
+        payment_interval,
+        revocable,
+    }
+    .data();
+    Instruction::new_with_bytes(ID, &data, metas)
+}
+
+/// Closes a revoked or fully claimed schedule, refunding the rent to the
+/// creator and removing it from the given index pages
+pub fn close(
+    creator: &Pubkey,
+    vesting: &Pubkey,
+    token_program: &Pubkey,
+    creator_index: Option<Pubkey>,
+    beneficiary_index: Option<Pubkey>,
+) -> Instruction {
+    let metas = accounts::Close {
+        vesting: *vesting,
+        vault: pda::vault_address(vesting).0,
+        creator_index,
+        beneficiary_index,
+        creator: *creator,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::Close {}.data(), metas)
+}
+
+/// Creates page `page` of `owner`'s schedule index, paid for by `owner`
+pub fn create_schedule_index(owner: &Pubkey, kind: IndexKind, page: u32) -> Instruction {
+    let metas = accounts::CreateScheduleIndex {
+        schedule_index: pda::schedule_index_address(kind, owner, page).0,
+        owner: *owner,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(
+        ID,
+        &instruction::CreateScheduleIndex { kind, page }.data(),
+        metas,
+    )
+}
+
+/// Removes a closed `vesting` account from an index page of `owner`, signed by
+/// anyone
+pub fn prune_schedule_index(
+    schedule_index: &Pubkey,
+    owner: &Pubkey,
+    vesting: &Pubkey,
+) -> Instruction {
+    let metas = accounts::PruneScheduleIndex {
+        schedule_index: *schedule_index,
+        owner: *owner,
+        vesting: *vesting,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::PruneScheduleIndex {}.data(), metas)
+}
+
+/// Removes `schedules` from an index page, open or not, signed by its `owner`
+pub fn remove_schedule_index_entries(
+    schedule_index: &Pubkey,
+    owner: &Pubkey,
+    schedules: Vec<Pubkey>,
+) -> Instruction {
+    let metas = accounts::RemoveScheduleIndexEntries {
+        schedule_index: *schedule_index,
+        owner: *owner,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(
+        ID,
+        &instruction::RemoveScheduleIndexEntries { schedules }.data(),
+        metas,
+    )
+}
+
+/// View returning a `Reconciliation` of the vault balance against what the
+/// schedule owes, to be simulated with `transaction::simulate_view`
+pub fn reconcile(vesting: &Pubkey) -> Instruction {
+    let metas = accounts::ReadOnlyReconcile {
+        vesting: *vesting,
+        vault: pda::vault_address(vesting).0,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::Reconcile {}.data(), metas)
+}
+
+/// Moves the tokens the vault holds beyond what the schedule owes to
+/// `recipient_account`
+pub fn sweep_surplus(
+    creator: &Pubkey,
+    vesting: &Pubkey,
+    recipient_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let metas = accounts::SweepSurplus {
+        vesting: *vesting,
+        vault: pda::vault_address(vesting).0,
+        recipient_account: *recipient_account,
+        creator: *creator,
+        mint: *mint,
+        token_program: *token_program,
+        config: pda::config_address().0,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority_address().0,
+        #[cfg(feature = "event-cpi")]
+        program: ID,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::SweepSurplus {}.data(), metas)
+}
+
+/// Sets who may rescue foreign tokens from the schedule, signed by both parties
+pub fn set_rescue_policy(
+    creator: &Pubkey,
+    beneficiary: &Pubkey,
+    vesting: &Pubkey,
+    policy: RescuePolicy,
+) -> Instruction {
+    let metas = accounts::SetRescuePolicy {
+        vesting: *vesting,
+        creator: *creator,
+        beneficiary: *beneficiary,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::SetRescuePolicy { policy }.data(), metas)
+}
+
+/// Empties `token_account`, held by the vesting PDA in a mint other than the
+/// vested one, into `destination` and closes it, refunding its rent to `authority`
+pub fn rescue_tokens(
+    authority: &Pubkey,
+    vesting: &Pubkey,
+    token_account: &Pubkey,
+    destination: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let metas = accounts::RescueTokens {
+        vesting: *vesting,
+        token_account: *token_account,
+        destination: *destination,
+        mint: *mint,
+        authority: *authority,
+        token_program: *token_program,
+        config: pda::config_address().0,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority_address().0,
+        #[cfg(feature = "event-cpi")]
+        program: ID,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::RescueTokens {}.data(), metas)
+}
+
+/// View returning a `VestingStatus` at `at`, or now, to be simulated with
+/// `transaction::simulate_view`
+pub fn vesting_status(vesting: &Pubkey, at: Option<i64>) -> Instruction {
+    let metas = accounts::ReadOnlyStatus { vesting: *vesting }.to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::VestingStatus { at }.data(), metas)
+}
+
+/// Creates a zero-copy schedule at `pda::zero_copy_vesting_address(authority,
+/// beneficiary, mint, id)`. Zero-copy schedules aren't indexed, so the index
+/// pages are ignored.
+pub fn initialize_zero_copy(
+    accounts: &InitializeAccounts,
+    params: InitializeParams,
+) -> Instruction {
+    let vesting = pda::zero_copy_vesting_address(
+        &accounts.authority,
+        &accounts.beneficiary,
+        &accounts.mint,
+        params.id,
+    )
+    .0;
+    let metas = accounts::InitializeVestingZeroCopy {
+        vesting,
+        vault: pda::vault_address(&vesting).0,
+        payer: accounts.payer,
+        authority: accounts.authority,
+        source_token_account: accounts.source_token_account,
+        beneficiary: accounts.beneficiary,
+        mint: accounts.mint,
+        token_program: accounts.token_program,
+        system_program: system_program::ID,
+        config: pda::config_address().0,
+        allowed_mint: pda::allowed_mint_address(&accounts.mint).0,
+        treasury: accounts.treasury,
+        treasury_token_account: accounts.treasury_token_account,
+        fee_waiver: pda::fee_waiver_address(&accounts.authority).0,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority_address().0,
+        #[cfg(feature = "event-cpi")]
+        program: ID,
+    }
+    .to_account_metas(None);
+    let data = instruction::InitializeZeroCopy {
+        id: params.id,
+        amount: params.amount,
+        start_time: params.start_time,
+        end_time: params.end_time,
+        cliff_percentage: params.cliff_percentage,
+        payment_interval: params.payment_interval,
+        name: params.name,
+        revocable: params.revocable,
+    }
+    .data();
+    Instruction::new_with_bytes(ID, &data, metas)
+}
+
+/// `claim` of a zero-copy schedule
+pub fn claim_zero_copy(
+    beneficiary: &Pubkey,
+    vesting: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let metas = accounts::ClaimZeroCopy {
+        vesting: *vesting,
+        vault: pda::vault_address(vesting).0,
+        beneficiary_token_account: get_associated_token_address_with_program_id(
+            beneficiary,
+            mint,
+            token_program,
+        ),
+        token_program: *token_program,
+        mint: *mint,
+        beneficiary: *beneficiary,
+        system_program: system_program::ID,
+        associated_token_program: associated_token::ID,
+        config: pda::config_address().0,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority_address().0,
+        #[cfg(feature = "event-cpi")]
+        program: ID,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::ClaimZeroCopy {}.data(), metas)
+}
+
+/// `revoke` of a zero-copy schedule
+pub fn revoke_zero_copy(
+    creator: &Pubkey,
+    vesting: &Pubkey,
+    recipient_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let metas = accounts::RevokeZeroCopy {
+        vesting: *vesting,
+        vault: pda::vault_address(vesting).0,
+        recipient_account: *recipient_account,
+        creator: *creator,
+        mint: *mint,
+        token_program: *token_program,
+        config: pda::config_address().0,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority_address().0,
+        #[cfg(feature = "event-cpi")]
+        program: ID,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::RevokeZeroCopy {}.data(), metas)
+}
+
+/// `estimate` of a zero-copy schedule
+pub fn estimate_zero_copy(signer: &Pubkey, vesting: &Pubkey) -> Instruction {
+    let metas = accounts::ReadOnlyClaimZeroCopy {
+        vesting: *vesting,
+        signer: *signer,
+    }
+    .to_account_metas(None);
+    Instruction::new_with_bytes(ID, &instruction::EstimateZeroCopy {}.data(), metas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+    use anchor_spl::token_2022;
+
+    #[test]
+    fn initialize_derives_accounts_and_encodes_params() {
+        let (authority, beneficiary, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let accounts = InitializeAccounts::new(authority, beneficiary, mint, token_2022::ID);
+        let params = InitializeParams {
+            id: 7,
+            amount: 1_000,
+            start_time: 10,
+            end_time: 20,
+            cliff_percentage: 5,
+            payment_interval: Some(2),
+            name: "Grant".to_string(),
+            revocable: true,
+        };
+        let ix = initialize(&accounts, params);
+
+        let vesting = pda::vesting_address(&authority, &beneficiary, &mint, 7).0;
+        assert_eq!(ix.program_id, ID);
+        assert_eq!(ix.accounts[0].pubkey, vesting);
+        assert!(ix.accounts[0].is_writable);
+        assert_eq!(ix.accounts[1].pubkey, pda::vault_address(&vesting).0);
+        assert!(ix.accounts[2].is_signer && ix.accounts[3].is_signer);
+        assert_eq!(&ix.data[..8], instruction::Initialize::DISCRIMINATOR);
+        assert_eq!(&ix.data[8..16], &7u64.to_le_bytes());
+    }
+
+    #[test]
+    fn missing_optional_accounts_are_replaced_by_the_program_id() {
+        let accounts = InitializeAccounts::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            token_2022::ID,
+        );
+        let ix = initialize(
+            &accounts,
+            InitializeParams {
+                id: 0,
+                amount: 1,
+                start_time: 0,
+                end_time: 1,
+                cliff_percentage: 0,
+                payment_interval: None,
+                name: String::new(),
+                revocable: false,
+            },
+        );
+        let treasury = &ix.accounts[11];
+        assert_eq!(treasury.pubkey, ID);
+        assert!(!treasury.is_writable);
+    }
+
+    #[test]
+    fn claim_uses_the_beneficiary_associated_token_account() {
+        let (beneficiary, mint, vesting) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = claim(&beneficiary, &vesting, &mint, &token_2022::ID);
+
+        assert_eq!(
+            ix.accounts[2].pubkey,
+            get_associated_token_address_with_program_id(&beneficiary, &mint, &token_2022::ID)
+        );
+        assert_eq!(ix.accounts[5].pubkey, beneficiary);
+        assert!(ix.accounts[5].is_signer);
+        assert_eq!(ix.data, instruction::Claim::DISCRIMINATOR);
+    }
+}
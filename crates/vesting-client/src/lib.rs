@@ -0,0 +1,13 @@
+//! Rust client for the vesting program: instruction builders, PDA helpers,
+//! account fetching and event parsing. Everything is built on the program's
+//! own account, instruction and event types, so it can't drift from it.
+
+pub mod accounts;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+pub mod transaction;
+
+pub use error::ClientError;
+pub use solana_vesting_program::{self as program, ID as PROGRAM_ID};
@@ -0,0 +1,165 @@
+//! Addresses of the program's accounts, derived from the seeds it exports
+
+use anchor_lang::prelude::Pubkey;
+use solana_vesting_program::{
+    IndexKind, ALLOWED_MINT_SEED, CONFIG_SEED, FEE_WAIVER_SEED, ID, SCHEDULE_INDEX_SEED,
+    VAULT_SEED, VESTING_SEED, ZERO_COPY_VESTING_SEED,
+};
+
+/// Seed of the PDA signing self-CPI events
+const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+pub fn vesting_address(
+    creator: &Pubkey,
+    beneficiary: &Pubkey,
+    mint: &Pubkey,
+    id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VESTING_SEED,
+            creator.as_ref(),
+            beneficiary.as_ref(),
+            mint.as_ref(),
+            &id.to_le_bytes(),
+        ],
+        &ID,
+    )
+}
+
+/// Address of a schedule created before IDs existed
+pub fn legacy_vesting_address(beneficiary: &Pubkey, mint: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VESTING_SEED,
+            beneficiary.as_ref(),
+            mint.as_ref(),
+            name.as_bytes(),
+        ],
+        &ID,
+    )
+}
+
+pub fn zero_copy_vesting_address(
+    creator: &Pubkey,
+    beneficiary: &Pubkey,
+    mint: &Pubkey,
+    id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ZERO_COPY_VESTING_SEED,
+            creator.as_ref(),
+            beneficiary.as_ref(),
+            mint.as_ref(),
+            &id.to_le_bytes(),
+        ],
+        &ID,
+    )
+}
+
+pub fn vault_address(vesting: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, vesting.as_ref()], &ID)
+}
+
+pub fn config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &ID)
+}
+
+pub fn allowed_mint_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ALLOWED_MINT_SEED, mint.as_ref()], &ID)
+}
+
+pub fn fee_waiver_address(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_WAIVER_SEED, creator.as_ref()], &ID)
+}
+
+pub fn schedule_index_address(kind: IndexKind, owner: &Pubkey, page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SCHEDULE_INDEX_SEED,
+            &[kind as u8],
+            owner.as_ref(),
+            &page.to_le_bytes(),
+        ],
+        &ID,
+    )
+}
+
+pub fn event_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_vesting_program::{Vesting, VESTING_RESERVED_LEN, VESTING_VERSION};
+
+    fn vesting(creator: Pubkey, beneficiary: Pubkey, mint: Pubkey, id: Option<u64>) -> Vesting {
+        Vesting {
+            beneficiary,
+            creator,
+            mint,
+            start_time: 0,
+            end_time: 100,
+            total_amount: 1_000,
+            claimed_amount: 0,
+            cliff_percentage: 0,
+            payment_interval: 0,
+            name: "Grant".to_string(),
+            revocable: true,
+            revoked_at: 0,
+            last_claimed_at: 0,
+            bump: 0,
+            paused_at: 0,
+            paused_duration: 0,
+            accelerated_amount: 0,
+            version: VESTING_VERSION,
+            id,
+            rescue_policy: Default::default(),
+            reserved: [0; VESTING_RESERVED_LEN],
+        }
+    }
+
+    // The program signs with `Vesting::with_signer_seeds`, the derived address has to match it
+    fn signer_address(vesting: &Vesting) -> Pubkey {
+        vesting.with_signer_seeds(|seeds| Pubkey::create_program_address(seeds[0], &ID).unwrap())
+    }
+
+    #[test]
+    fn vesting_address_matches_program_signer_seeds() {
+        let (creator, beneficiary, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let (address, bump) = vesting_address(&creator, &beneficiary, &mint, 42);
+
+        let mut schedule = vesting(creator, beneficiary, mint, Some(42));
+        schedule.bump = bump;
+        assert_eq!(signer_address(&schedule), address);
+    }
+
+    #[test]
+    fn legacy_vesting_address_matches_program_signer_seeds() {
+        let (beneficiary, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (address, bump) = legacy_vesting_address(&beneficiary, &mint, "Grant");
+
+        let mut schedule = vesting(Pubkey::new_unique(), beneficiary, mint, None);
+        schedule.bump = bump;
+        assert_eq!(signer_address(&schedule), address);
+    }
+
+    #[test]
+    fn addresses_differ_per_creator_and_id() {
+        let (beneficiary, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (creator, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let address = vesting_address(&creator, &beneficiary, &mint, 1).0;
+        assert_ne!(address, vesting_address(&other, &beneficiary, &mint, 1).0);
+        assert_ne!(address, vesting_address(&creator, &beneficiary, &mint, 2).0);
+        assert_ne!(
+            address,
+            zero_copy_vesting_address(&creator, &beneficiary, &mint, 1).0
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Sending instructions and simulating views
+
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_client::{rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
+use solana_sdk::{
+    instruction::Instruction,
+    program::MAX_RETURN_DATA,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::error::{ClientError, Result};
+
+/// Sends `instructions` in one transaction paid by `payer` and waits for its confirmation
+pub fn send(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    signers: &[&Keypair],
+    instructions: &[Instruction],
+) -> Result<Signature> {
+    let mut all_signers = vec![payer];
+    all_signers.extend(signers.iter().filter(|s| s.pubkey() != payer.pubkey()));
+    let blockhash = rpc.get_latest_blockhash()?;
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    Ok(rpc.send_and_confirm_transaction(&transaction)?)
+}
+
+/// Simulates a view instruction such as `instructions::estimate` and decodes
+/// what it returned. `fee_payer` doesn't have to sign.
+pub fn simulate_view<T: AnchorDeserialize>(
+    rpc: &RpcClient,
+    fee_payer: &Pubkey,
+    instruction: Instruction,
+) -> Result<T> {
+    let transaction = Transaction::new_with_payer(&[instruction], Some(fee_payer));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc
+        .simulate_transaction_with_config(&transaction, config)?
+        .value;
+    if let Some(err) = result.err {
+        return Err(ClientError::Simulation(err.to_string()));
+    }
+    let return_data = result.return_data.ok_or(ClientError::NoReturnData)?;
+    let mut data = STANDARD
+        .decode(return_data.data.0)
+        .map_err(|e| ClientError::InvalidReturnData(e.to_string()))?;
+    // The runtime trims trailing zeros from return data
+    data.resize(MAX_RETURN_DATA, 0);
+    T::deserialize(&mut data.as_slice()).map_err(|e| ClientError::InvalidReturnData(e.to_string()))
+}
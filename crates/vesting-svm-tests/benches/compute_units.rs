@@ -0,0 +1,431 @@
+//! Compute units of every schedule instruction, across token programs and
+//! schedule shapes, compared against a recorded baseline. Config, allowlist,
+//! fee waiver and index instructions aren't measured.
+//!
+//! ```sh
+//! cargo bench --manifest-path crates/vesting-svm-tests/Cargo.toml -- [--threshold 5] [--update-baseline]
+//! ```
+//!
+//! Writes `target/cu-report.json` and fails if an instruction uses more than
+//! `--threshold` percent over its baseline in `benches/cu-baseline.json`, if
+//! the baseline is missing, or if it doesn't match the measured instructions.
+//! `--update-baseline` records the current numbers instead.
+
+#![allow(clippy::result_large_err)]
+
+use std::{collections::BTreeMap, fs, path::PathBuf, process::ExitCode};
+
+use anchor_spl::{token, token_2022};
+use serde_json::{json, Value};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use vesting_client::{
+    instructions::{self, InitializeAccounts, InitializeParams},
+    pda,
+    program::{Acceleration, RescuePolicy},
+};
+use vesting_svm_tests::{TestEnv, TxResult, GENESIS_TIME};
+
+const DAY: i64 = 86_400;
+const START: i64 = GENESIS_TIME;
+const END: i64 = START + 4 * 365 * DAY;
+
+/// Name, cliff percentage and payment interval of each schedule shape
+const SHAPES: [(&str, u8, Option<i64>); 3] = [
+    ("linear", 0, None),
+    ("monthly", 0, Some(30 * DAY)),
+    ("cliff-linear", 25, None),
+];
+
+const TOKEN_PROGRAMS: [(&str, Pubkey); 2] =
+    [("spl-token", token::ID), ("token-2022", token_2022::ID)];
+
+struct Options {
+    /// Allowed increase over the baseline, in percent
+    threshold: f64,
+    baseline: PathBuf,
+    report: PathBuf,
+    update_baseline: bool,
+}
+
+fn parse_options() -> Options {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let mut options = Options {
+        threshold: 5.0,
+        baseline: manifest_dir.join("benches/cu-baseline.json"),
+        report: manifest_dir.join("../../target/cu-report.json"),
+        update_baseline: false,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| panic!("{arg} needs a value"));
+        match arg.as_str() {
+            "--threshold" => options.threshold = value().parse().expect("invalid --threshold"),
+            "--baseline" => options.baseline = value().into(),
+            "--report" => options.report = value().into(),
+            "--update-baseline" => options.update_baseline = true,
+            // Passed by `cargo bench`
+            "--bench" => {}
+            _ => panic!("unknown option {arg}"),
+        }
+    }
+    options
+}
+
+fn units(result: TxResult, what: &str) -> u64 {
+    match result {
+        Ok(meta) => meta.compute_units_consumed,
+        Err(e) => panic!("{what} failed: {:?}\n{}", e.err, e.meta.logs.join("\n")),
+    }
+}
+
+fn params(cliff_percentage: u8, payment_interval: Option<i64>) -> InitializeParams {
+    InitializeParams {
+        id: 1,
+        amount: 1_000_000_000,
+        start_time: START,
+        end_time: END,
+        cliff_percentage,
+        payment_interval,
+        name: "Benchmark".to_string(),
+        revocable: true,
+    }
+}
+
+/// Runs one schedule through its whole life, measuring each step
+fn measure(
+    token_program: Pubkey,
+    cliff_percentage: u8,
+    payment_interval: Option<i64>,
+) -> Vec<(&'static str, u64)> {
+    let mut env = TestEnv::new();
+    let creator = env.funded_keypair();
+    let beneficiary = env.funded_keypair();
+    let mint = env.create_mint(&token_program, 6);
+    let source = env.fund(&creator.pubkey(), &mint, &token_program, 1_000_000_000);
+    let vesting = pda::vesting_address(&creator.pubkey(), &beneficiary.pubkey(), &mint, 1).0;
+
+    let initialize = instructions::initialize(
+        &InitializeAccounts::new(creator.pubkey(), beneficiary.pubkey(), mint, token_program),
+        params(cliff_percentage, payment_interval),
+    );
+    let claim = instructions::claim(&beneficiary.pubkey(), &vesting, &mint, &token_program);
+    let send = |env: &mut TestEnv, instruction: Instruction, signer: &Keypair, what| {
+        units(env.send(&[instruction], &[signer]), what)
+    };
+
+    let mut measured = Vec::new();
+    measured.push((
+        "initialize",
+        send(&mut env, initialize, &creator, "initialize"),
+    ));
+
+    env.warp_to(START + 365 * DAY);
+    let estimate = instructions::estimate(&env.payer.pubkey(), &vesting);
+    measured.push((
+        "estimate",
+        units(env.simulate(&[estimate], &[]), "estimate"),
+    ));
+    let status = instructions::vesting_status(&vesting, None);
+    measured.push((
+        "vesting_status",
+        units(env.simulate(&[status], &[]), "vesting_status"),
+    ));
+    measured.push((
+        "claim_creating_ata",
+        send(&mut env, claim.clone(), &beneficiary, "first claim"),
+    ));
+
+    let creator_key = creator.pubkey();
+    let pause = instructions::pause(&creator_key, &vesting);
+    measured.push(("pause", send(&mut env, pause, &creator, "pause")));
+    env.warp_to(START + 365 * DAY + 30 * DAY);
+    let resume = instructions::resume(&creator_key, &vesting);
+    measured.push(("resume", send(&mut env, resume, &creator, "resume")));
+    let accelerate = instructions::accelerate(
+        &creator_key,
+        &vesting,
+        Acceleration::Bonus { amount: 1_000 },
+    );
+    measured.push((
+        "accelerate",
+        send(&mut env, accelerate, &creator, "accelerate"),
+    ));
+    let amend = instructions::amend_schedule(
+        &creator_key,
+        &beneficiary.pubkey(),
+        &vesting,
+        END - 365 * DAY,
+        cliff_percentage,
+        payment_interval,
+        true,
+    );
+    measured.push((
+        "amend_schedule",
+        units(
+            env.send(&[amend], &[&creator, &beneficiary]),
+            "amend_schedule",
+        ),
+    ));
+
+    env.warp_to(START + 2 * 365 * DAY);
+    measured.push(("claim", send(&mut env, claim, &beneficiary, "second claim")));
+
+    // Tokens sent straight to the vault
+    env.mint_to(
+        &pda::vault_address(&vesting).0,
+        &mint,
+        &token_program,
+        1_000,
+    );
+    let reconcile = instructions::reconcile(&vesting);
+    measured.push((
+        "reconcile",
+        units(env.simulate(&[reconcile], &[]), "reconcile"),
+    ));
+    let sweep = instructions::sweep_surplus(&creator_key, &vesting, &source, &mint, &token_program);
+    measured.push((
+        "sweep_surplus",
+        send(&mut env, sweep, &creator, "sweep_surplus"),
+    ));
+
+    // Tokens of another mint sent to the vesting PDA
+    let foreign_mint = env.create_mint(&token_program, 9);
+    let stuck = env.fund(&vesting, &foreign_mint, &token_program, 1_000);
+    let destination = env.fund(&creator_key, &foreign_mint, &token_program, 0);
+    let policy = instructions::set_rescue_policy(
+        &creator_key,
+        &beneficiary.pubkey(),
+        &vesting,
+        RescuePolicy::CreatorOnly,
+    );
+    measured.push((
+        "set_rescue_policy",
+        units(
+            env.send(&[policy], &[&creator, &beneficiary]),
+            "set_rescue_policy",
+        ),
+    ));
+    let rescue = instructions::rescue_tokens(
+        &creator_key,
+        &vesting,
+        &stuck,
+        &destination,
+        &foreign_mint,
+        &token_program,
+    );
+    measured.push((
+        "rescue_tokens",
+        send(&mut env, rescue, &creator, "rescue_tokens"),
+    ));
+
+    let revoke = instructions::revoke(&creator.pubkey(), &vesting, &source, &mint, &token_program);
+    measured.push(("revoke", send(&mut env, revoke, &creator, "revoke")));
+    let close = instructions::close(&creator.pubkey(), &vesting, &token_program, None, None);
+    measured.push(("close", send(&mut env, close, &creator, "close")));
+    measured
+}
+
+/// Runs one zero-copy schedule through its whole life, measuring each step
+fn measure_zero_copy(
+    token_program: Pubkey,
+    cliff_percentage: u8,
+    payment_interval: Option<i64>,
+) -> Vec<(&'static str, u64)> {
+    let mut env = TestEnv::new();
+    let creator = env.funded_keypair();
+    let beneficiary = env.funded_keypair();
+    let mint = env.create_mint(&token_program, 6);
+    let source = env.fund(&creator.pubkey(), &mint, &token_program, 1_000_000_000);
+    let vesting =
+        pda::zero_copy_vesting_address(&creator.pubkey(), &beneficiary.pubkey(), &mint, 1).0;
+
+    let initialize = instructions::initialize_zero_copy(
+        &InitializeAccounts::new(creator.pubkey(), beneficiary.pubkey(), mint, token_program),
+        params(cliff_percentage, payment_interval),
+    );
+    let claim =
+        instructions::claim_zero_copy(&beneficiary.pubkey(), &vesting, &mint, &token_program);
+    let send = |env: &mut TestEnv, instruction: Instruction, signer: &Keypair, what| {
+        units(env.send(&[instruction], &[signer]), what)
+    };
+
+    let mut measured = Vec::new();
+    measured.push((
+        "initialize_zero_copy",
+        send(&mut env, initialize, &creator, "initialize_zero_copy"),
+    ));
+
+    env.warp_to(START + 365 * DAY);
+    let estimate = instructions::estimate_zero_copy(&env.payer.pubkey(), &vesting);
+    measured.push((
+        "estimate_zero_copy",
+        units(env.simulate(&[estimate], &[]), "estimate_zero_copy"),
+    ));
+    measured.push((
+        "claim_zero_copy_creating_ata",
+        send(
+            &mut env,
+            claim.clone(),
+            &beneficiary,
+            "first zero-copy claim",
+        ),
+    ));
+
+    env.warp_to(START + 2 * 365 * DAY);
+    measured.push((
+        "claim_zero_copy",
+        send(&mut env, claim, &beneficiary, "second zero-copy claim"),
+    ));
+
+    let revoke =
+        instructions::revoke_zero_copy(&creator.pubkey(), &vesting, &source, &mint, &token_program);
+    measured.push((
+        "revoke_zero_copy",
+        send(&mut env, revoke, &creator, "revoke_zero_copy"),
+    ));
+    measured
+}
+
+fn main() -> ExitCode {
+    let options = parse_options();
+
+    let mut measured = BTreeMap::new();
+    for (program_name, token_program) in TOKEN_PROGRAMS {
+        for (shape, cliff_percentage, payment_interval) in SHAPES {
+            let runs = measure(token_program, cliff_percentage, payment_interval)
+                .into_iter()
+                .chain(measure_zero_copy(
+                    token_program,
+                    cliff_percentage,
+                    payment_interval,
+                ));
+            for (instruction, units) in runs {
+                measured.insert(format!("{program_name}/{shape}/{instruction}"), units);
+            }
+        }
+    }
+
+    if options.update_baseline {
+        fs::write(
+            &options.baseline,
+            serde_json::to_string_pretty(&measured).unwrap() + "\n",
+        )
+        .unwrap();
+        println!(
+            "recorded {} measurements in {}",
+            measured.len(),
+            options.baseline.display()
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    let baseline: BTreeMap<String, u64> = match fs::read_to_string(&options.baseline) {
+        Ok(json) => serde_json::from_str(&json).expect("invalid baseline"),
+        Err(e) => {
+            eprintln!(
+                "no baseline at {} ({e}), record one with --update-baseline",
+                options.baseline.display()
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    // Baseline entries no longer measured, after a rename or a removed step
+    let unmeasured: Vec<&String> = baseline
+        .keys()
+        .filter(|name| !measured.contains_key(*name))
+        .collect();
+
+    let mut results = Vec::new();
+    let mut regressions = Vec::new();
+    let mut missing = Vec::new();
+    println!(
+        "{:<44} {:>8} {:>8} {:>8}",
+        "instruction", "units", "baseline", "change"
+    );
+    for (name, &units) in &measured {
+        let base = baseline.get(name).copied();
+        let change = base
+            .filter(|&base| base > 0)
+            .map(|base| (units as f64 - base as f64) * 100.0 / base as f64);
+        let status = match change {
+            None => "missing",
+            Some(change) if change > options.threshold => "regressed",
+            Some(change) if change < 0.0 => "improved",
+            Some(_) => "ok",
+        };
+        match status {
+            "regressed" => regressions.push(name.clone()),
+            "missing" => missing.push(name.clone()),
+            _ => {}
+        }
+        println!(
+            "{name:<44} {units:>8} {:>8} {:>8} {}",
+            base.map(|base| base.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            change
+                .map(|change| format!("{change:+.1}%"))
+                .unwrap_or_default(),
+            match status {
+                "regressed" => "REGRESSED",
+                "missing" => "NO BASELINE",
+                _ => "",
+            },
+        );
+        results.push(json!({
+            "name": name,
+            "compute_units": units,
+            "baseline": base,
+            "change_percent": change,
+            "status": status,
+        }));
+    }
+
+    let report: Value = json!({
+        "threshold_percent": options.threshold,
+        "results": results,
+        "regressions": regressions,
+        "missing": missing,
+        "unmeasured": unmeasured,
+    });
+    if let Some(dir) = options.report.parent() {
+        fs::create_dir_all(dir).unwrap();
+    }
+    fs::write(
+        &options.report,
+        serde_json::to_string_pretty(&report).unwrap() + "\n",
+    )
+    .unwrap();
+    println!("report written to {}", options.report.display());
+
+    if !regressions.is_empty() {
+        eprintln!(
+            "{} instructions regressed by more than {}%: {}",
+            regressions.len(),
+            options.threshold,
+            regressions.join(", ")
+        );
+    }
+    if !missing.is_empty() {
+        eprintln!(
+            "{} instructions have no baseline, record them with --update-baseline: {}",
+            missing.len(),
+            missing.join(", ")
+        );
+    }
+    if !unmeasured.is_empty() {
+        eprintln!(
+            "{} baseline entries weren't measured, drop them with --update-baseline: {}",
+            unmeasured.len(),
+            unmeasured
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    if regressions.is_empty() && missing.is_empty() && unmeasured.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
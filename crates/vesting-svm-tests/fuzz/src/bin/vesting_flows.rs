@@ -0,0 +1,205 @@
+//! Drives random sequences of initialize, claim, revoke and estimate against
+//! the compiled program, checking after every step that no token was created
+//! or lost: the vault, what the beneficiary claimed and what was revoked back
+//! to the creator always add up to the funded amount.
+//!
+//! Run with `cargo hfuzz run vesting_flows` after `anchor build`. Against a
+//! program built with the `invariant-checks` feature, any transaction the
+//! program rejects with `InvariantViolation` is reported as a crash too.
+
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use arbitrary::{Arbitrary, Unstructured};
+use honggfuzz::fuzz;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use solana_vesting_program::{utils, VestingError};
+use vesting_client::{
+    instructions::{self, InitializeAccounts, InitializeParams},
+    pda,
+};
+use vesting_svm_tests::{error_code, TestEnv, TxResult, GENESIS_TIME};
+
+#[derive(Arbitrary, Debug)]
+struct Scenario {
+    token_2022: bool,
+    amount: u64,
+    /// Start relative to the genesis clock
+    start_offset: i32,
+    duration: u32,
+    cliff_percentage: u8,
+    payment_interval: Option<u32>,
+    revocable: bool,
+    actions: Vec<Action>,
+}
+
+#[derive(Arbitrary, Debug)]
+enum Action {
+    Warp(u32),
+    Claim,
+    Revoke,
+    Estimate,
+    /// A claim signed by someone other than the beneficiary
+    ForeignClaim,
+}
+
+struct Accounts {
+    token_program: Pubkey,
+    mint: Pubkey,
+    beneficiary: Keypair,
+    source: Pubkey,
+    vesting: Pubkey,
+    vault: Pubkey,
+}
+
+fn main() {
+    loop {
+        fuzz!(|data: &[u8]| {
+            if let Ok(scenario) = Scenario::arbitrary(&mut Unstructured::new(data)) {
+                run(scenario);
+            }
+        });
+    }
+}
+
+fn run(scenario: Scenario) {
+    let mut env = TestEnv::new();
+    let token_program = if scenario.token_2022 {
+        anchor_spl::token_2022::ID
+    } else {
+        anchor_spl::token::ID
+    };
+    let creator = env.funded_keypair();
+    let beneficiary = env.funded_keypair();
+    let mint = env.create_mint(&token_program, 6);
+    let source = env.fund(&creator.pubkey(), &mint, &token_program, scenario.amount);
+
+    let initialize = instructions::initialize(
+        &InitializeAccounts::new(creator.pubkey(), beneficiary.pubkey(), mint, token_program),
+        InitializeParams {
+            id: 1,
+            amount: scenario.amount,
+            start_time: GENESIS_TIME + scenario.start_offset as i64,
+            end_time: GENESIS_TIME + scenario.start_offset as i64 + scenario.duration as i64,
+            cliff_percentage: scenario.cliff_percentage,
+            payment_interval: scenario.payment_interval.map(i64::from),
+            name: "Fuzz".to_string(),
+            revocable: scenario.revocable,
+        },
+    );
+    let vesting = pda::vesting_address(&creator.pubkey(), &beneficiary.pubkey(), &mint, 1).0;
+    let accounts = Accounts {
+        token_program,
+        mint,
+        source,
+        vault: pda::vault_address(&vesting).0,
+        vesting,
+        beneficiary,
+    };
+
+    if !landed(env.send(&[initialize], &[&creator])) {
+        // Rejected schedules must leave the funds where they were
+        assert_eq!(env.token_balance(&accounts.source), Some(scenario.amount));
+        assert!(env.vesting(&accounts.vesting).is_none());
+        return;
+    }
+    check_conservation(&env, &accounts, scenario.amount);
+
+    for action in scenario.actions {
+        match action {
+            Action::Warp(seconds) => {
+                let now = env.now();
+                env.warp_to(now + seconds as i64);
+            }
+            Action::Claim => {
+                let beneficiary = accounts.beneficiary.insecure_clone();
+                landed(env.send(
+                    &[instructions::claim(
+                        &beneficiary.pubkey(),
+                        &accounts.vesting,
+                        &accounts.mint,
+                        &accounts.token_program,
+                    )],
+                    &[&beneficiary],
+                ));
+            }
+            Action::Revoke => {
+                let revoked = landed(env.send(
+                    &[instructions::revoke(
+                        &creator.pubkey(),
+                        &accounts.vesting,
+                        &accounts.source,
+                        &accounts.mint,
+                        &accounts.token_program,
+                    )],
+                    &[&creator],
+                ));
+                assert!(scenario.revocable || !revoked);
+            }
+            Action::Estimate => {
+                let vesting = env.vesting(&accounts.vesting).unwrap();
+                let estimate: u64 = env.view(
+                    instructions::estimate(&env.payer.pubkey(), &accounts.vesting),
+                    &[],
+                );
+                // The program and this crate's copy of the math agree
+                let expected = utils::calculate_claimable_amount(&vesting, env.now()).unwrap();
+                assert_eq!(estimate, expected);
+            }
+            Action::ForeignClaim => {
+                let impostor = env.funded_keypair();
+                let claimed = landed(env.send(
+                    &[instructions::claim(
+                        &impostor.pubkey(),
+                        &accounts.vesting,
+                        &accounts.mint,
+                        &accounts.token_program,
+                    )],
+                    &[&impostor],
+                ));
+                assert!(!claimed);
+            }
+        }
+        check_conservation(&env, &accounts, scenario.amount);
+    }
+}
+
+/// Whether the transaction went through, unless the program caught a broken
+/// invariant itself
+fn landed(result: TxResult) -> bool {
+    if let Err(failed) = &result {
+        assert_ne!(
+            error_code(failed),
+            Some(VestingError::InvariantViolation.into()),
+            "{}",
+            failed.meta.logs.join("\n")
+        );
+    }
+    result.is_ok()
+}
+
+/// vault + claimed + revoked == funded, and the schedule books what left the vault
+fn check_conservation(env: &TestEnv, accounts: &Accounts, funded: u64) {
+    let vault = env.token_balance(&accounts.vault).unwrap_or(0);
+    let claimed = env
+        .token_balance(&get_associated_token_address_with_program_id(
+            &accounts.beneficiary.pubkey(),
+            &accounts.mint,
+            &accounts.token_program,
+        ))
+        .unwrap_or(0);
+    let revoked = env.token_balance(&accounts.source).unwrap_or(0);
+    assert_eq!(
+        vault as u128 + claimed as u128 + revoked as u128,
+        funded as u128,
+        "vault {vault} + claimed {claimed} + revoked {revoked} != funded {funded}"
+    );
+
+    let vesting = env.vesting(&accounts.vesting).unwrap();
+    assert_eq!(vesting.claimed_amount, claimed);
+    assert!(vesting.claimed_amount <= vesting.total_amount);
+    if vesting.revoked_at == 0 {
+        assert_eq!(vault, funded - claimed);
+    } else {
+        assert_eq!(vault, 0);
+        assert_eq!(revoked, funded - claimed);
+    }
+}
@@ -0,0 +1,278 @@
+//! In-process test harness for the vesting program
+//!
+//! Runs the compiled `solana_vesting_program.so` in LiteSVM, with the SPL
+//! Token and Token-2022 programs it ships with, so tests need neither a
+//! validator nor Node. Build the program first with
+//! `anchor build -p solana_vesting_program -- --features event-cpi`, or point
+//! `SBF_OUT_DIR` at the directory holding the `.so`.
+
+// Transactions return LiteSVM's own error, whole, so tests can inspect the logs
+#![allow(clippy::result_large_err)]
+
+use std::path::PathBuf;
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorDeserialize};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address_with_program_id,
+        spl_associated_token_account::instruction::create_associated_token_account,
+    },
+    token_2022::spl_token_2022::{self, instruction as token_instruction},
+    token_interface::TokenAccount,
+};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use solana_vesting_program::{Config, Vesting, CONFIG_RESERVED_LEN, CONFIG_VERSION, ID};
+use vesting_client::{events::VestingEvent, pda};
+
+pub use litesvm;
+
+/// Where `anchor build` writes the program
+fn program_path() -> PathBuf {
+    let dir = std::env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"));
+    dir.join("solana_vesting_program.so")
+}
+
+/// The clock every test starts at
+pub const GENESIS_TIME: i64 = 1_700_000_000;
+
+pub type TxResult = Result<TransactionMetadata, FailedTransactionMetadata>;
+
+pub struct TestEnv {
+    pub svm: LiteSVM,
+    /// Funded key paying for setup transactions, also the config admin
+    pub payer: Keypair,
+}
+
+impl TestEnv {
+    /// An SVM with the program loaded, a default config and the clock at
+    /// `GENESIS_TIME`
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        let path = program_path();
+        svm.add_program_from_file(ID, &path).unwrap_or_else(|e| {
+            panic!(
+                "failed to load {}, run `anchor build` first: {e}",
+                path.display()
+            )
+        });
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 100 * LAMPORTS_PER_SOL)
+            .unwrap();
+
+        let mut env = Self { svm, payer };
+        env.warp_to(GENESIS_TIME);
+        // `initialize_config` needs the program's upgrade authority, which a
+        // program loaded from a file doesn't have, so the account is written
+        // directly
+        let admin = env.payer.pubkey();
+        env.set_config(Config {
+            admin,
+            paused: false,
+            fee_lamports: 0,
+            fee_bps: 0,
+            treasury: admin,
+            mint_allowlist_enabled: false,
+            bump: pda::config_address().1,
+            pending_admin: None,
+            version: CONFIG_VERSION,
+            reserved: [0; CONFIG_RESERVED_LEN],
+        });
+        env
+    }
+
+    pub fn set_config(&mut self, config: Config) {
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        self.set_program_account(pda::config_address().0, data);
+    }
+
+    /// Writes a rent-exempt account owned by the program
+    pub fn set_program_account(&mut self, address: Pubkey, data: Vec<u8>) {
+        let account = Account {
+            lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm.set_account(address, account).unwrap();
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    /// Moves the clock, leaving the slot where it is
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        let clock = Clock {
+            unix_timestamp,
+            ..self.svm.get_sysvar::<Clock>()
+        };
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// A new key holding some SOL
+    pub fn funded_keypair(&mut self) -> Keypair {
+        let keypair = Keypair::new();
+        self.svm
+            .airdrop(&keypair.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        keypair
+    }
+
+    /// A transaction of `instructions` signed by the payer and `signers`
+    fn sign(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Transaction {
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend(
+            signers
+                .iter()
+                .copied()
+                .filter(|s| s.pubkey() != self.payer.pubkey()),
+        );
+        Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash(),
+        )
+    }
+
+    /// Signs with the payer and `signers` and sends `instructions`. The
+    /// blockhash is expired afterwards, so sending the same instructions
+    /// again is a new transaction.
+    pub fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> TxResult {
+        let transaction = self.sign(instructions, signers);
+        let result = self.svm.send_transaction(transaction);
+        self.svm.expire_blockhash();
+        result
+    }
+
+    /// Runs `instructions` without committing anything
+    pub fn simulate(&self, instructions: &[Instruction], signers: &[&Keypair]) -> TxResult {
+        let transaction = self.sign(instructions, signers);
+        self.svm.simulate_transaction(transaction)
+    }
+
+    /// Simulates a view instruction and decodes what it returned
+    pub fn view<T: AnchorDeserialize>(&self, instruction: Instruction, signers: &[&Keypair]) -> T {
+        let meta = self
+            .simulate(&[instruction], signers)
+            .unwrap_or_else(|e| panic!("view failed: {:?}\n{}", e.err, e.meta.logs.join("\n")));
+        let mut data = meta.return_data.data;
+        // The runtime trims trailing zeros from return data
+        data.resize(solana_sdk::program::MAX_RETURN_DATA, 0);
+        T::deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    /// Creates a mint of `token_program` with the payer as mint authority
+    pub fn create_mint(&mut self, token_program: &Pubkey, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer.pubkey();
+        let len = spl_token_2022::state::Mint::LEN;
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                self.svm.minimum_balance_for_rent_exemption(len),
+                len as u64,
+                token_program,
+            ),
+            token_instruction::initialize_mint2(
+                token_program,
+                &mint.pubkey(),
+                &payer,
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ];
+        self.send(&instructions, &[&mint]).unwrap();
+        mint.pubkey()
+    }
+
+    /// Creates `owner`'s associated token account and mints `amount` into it
+    pub fn fund(
+        &mut self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        amount: u64,
+    ) -> Pubkey {
+        let payer = self.payer.pubkey();
+        let account = get_associated_token_address_with_program_id(owner, mint, token_program);
+        let instructions = [
+            create_associated_token_account(&payer, owner, mint, token_program),
+            token_instruction::mint_to(token_program, mint, &account, &payer, &[], amount).unwrap(),
+        ];
+        self.send(&instructions, &[]).unwrap();
+        account
+    }
+
+    /// Mints `amount` straight into an existing token account, the way tokens
+    /// sent from outside the program land
+    pub fn mint_to(
+        &mut self,
+        account: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        amount: u64,
+    ) {
+        let payer = self.payer.pubkey();
+        let instruction =
+            token_instruction::mint_to(token_program, mint, account, &payer, &[], amount).unwrap();
+        self.send(&[instruction], &[]).unwrap();
+    }
+
+    /// Balance of a token account of either token program, `None` if it
+    /// doesn't exist
+    pub fn token_balance(&self, account: &Pubkey) -> Option<u64> {
+        let account = self.svm.get_account(account)?;
+        let token_account = TokenAccount::try_deserialize(&mut account.data.as_slice()).unwrap();
+        Some(token_account.amount)
+    }
+
+    pub fn vesting(&self, address: &Pubkey) -> Option<Vesting> {
+        let account = self.svm.get_account(address)?;
+        Some(Vesting::try_deserialize(&mut account.data.as_slice()).unwrap())
+    }
+}
+
+impl Default for TestEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Events emitted through the `event-cpi` self-CPI of a transaction
+pub fn events(meta: &TransactionMetadata) -> Vec<VestingEvent> {
+    meta.inner_instructions
+        .iter()
+        .flatten()
+        .filter_map(|inner| VestingEvent::decode_cpi(&inner.instruction.data))
+        .collect()
+}
+
+/// The custom error code a failed transaction returned, e.g. an Anchor error
+pub fn error_code(failed: &FailedTransactionMetadata) -> Option<u32> {
+    match failed.err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+        _ => None,
+    }
+}
@@ -0,0 +1,502 @@
+//! End-to-end tests of initialize, claim, revoke, estimate, the surplus sweep
+//! and token rescue, run once for SPL Token and once for Token-2022
+
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::error::ErrorCode;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use solana_vesting_program::{
+    ClaimResult, Reconciliation, RescuePolicy, VestingError, VestingStatus, EVENT_SCHEMA_VERSION,
+};
+use vesting_client::{
+    events::VestingEvent,
+    instructions::{self, InitializeAccounts, InitializeParams},
+    pda,
+};
+use vesting_svm_tests::{error_code, events, TestEnv, GENESIS_TIME};
+
+const AMOUNT: u64 = 1_000_000;
+const START: i64 = GENESIS_TIME + 100;
+const END: i64 = START + 1_000;
+
+struct Schedule {
+    env: TestEnv,
+    token_program: Pubkey,
+    mint: Pubkey,
+    creator: Keypair,
+    beneficiary: Keypair,
+    vesting: Pubkey,
+    source: Pubkey,
+}
+
+impl Schedule {
+    fn claim(&mut self) -> vesting_svm_tests::TxResult {
+        let instruction = instructions::claim(
+            &self.beneficiary.pubkey(),
+            &self.vesting,
+            &self.mint,
+            &self.token_program,
+        );
+        let beneficiary = self.beneficiary.insecure_clone();
+        self.env.send(&[instruction], &[&beneficiary])
+    }
+
+    fn revoke(&mut self) -> vesting_svm_tests::TxResult {
+        let instruction = instructions::revoke(
+            &self.creator.pubkey(),
+            &self.vesting,
+            &self.source,
+            &self.mint,
+            &self.token_program,
+        );
+        let creator = self.creator.insecure_clone();
+        self.env.send(&[instruction], &[&creator])
+    }
+
+    fn sweep_surplus(&mut self, signer: &Keypair) -> vesting_svm_tests::TxResult {
+        let instruction = instructions::sweep_surplus(
+            &signer.pubkey(),
+            &self.vesting,
+            &self.source,
+            &self.mint,
+            &self.token_program,
+        );
+        self.env.send(&[instruction], &[signer])
+    }
+
+    fn reconcile(&mut self) -> Reconciliation {
+        self.env.view(instructions::reconcile(&self.vesting), &[])
+    }
+
+    fn estimate(&mut self) -> u64 {
+        let instruction = instructions::estimate(&self.env.payer.pubkey(), &self.vesting);
+        self.env.view(instruction, &[])
+    }
+
+    fn beneficiary_balance(&self) -> Option<u64> {
+        self.env
+            .token_balance(&get_associated_token_address_with_program_id(
+                &self.beneficiary.pubkey(),
+                &self.mint,
+                &self.token_program,
+            ))
+    }
+}
+
+fn params(
+    cliff_percentage: u8,
+    payment_interval: Option<i64>,
+    revocable: bool,
+) -> InitializeParams {
+    InitializeParams {
+        id: 1,
+        amount: AMOUNT,
+        start_time: START,
+        end_time: END,
+        cliff_percentage,
+        payment_interval,
+        name: "Test".to_string(),
+        revocable,
+    }
+}
+
+/// Creates a schedule of `AMOUNT` tokens vesting from `START` to `END`
+fn setup(token_program: Pubkey, params: InitializeParams) -> Schedule {
+    let mut env = TestEnv::new();
+    let creator = env.funded_keypair();
+    let beneficiary = env.funded_keypair();
+    let mint = env.create_mint(&token_program, 6);
+    let source = env.fund(&creator.pubkey(), &mint, &token_program, AMOUNT);
+
+    let accounts =
+        InitializeAccounts::new(creator.pubkey(), beneficiary.pubkey(), mint, token_program);
+    let vesting =
+        pda::vesting_address(&creator.pubkey(), &beneficiary.pubkey(), &mint, params.id).0;
+    let meta = env
+        .send(
+            &[instructions::initialize(&accounts, params.clone())],
+            &[&creator],
+        )
+        .unwrap();
+
+    let emitted = events(&meta);
+    let [VestingEvent::Initialized(event)] = emitted.as_slice() else {
+        panic!("expected one VestingInitialized event");
+    };
+    assert_eq!(event.schema_version, EVENT_SCHEMA_VERSION);
+    assert_eq!(event.vesting, vesting);
+    assert_eq!(event.creator, creator.pubkey());
+    assert_eq!(event.beneficiary, beneficiary.pubkey());
+    assert_eq!(event.mint, mint);
+    assert_eq!(event.total_amount, params.amount);
+
+    Schedule {
+        env,
+        token_program,
+        mint,
+        creator,
+        beneficiary,
+        vesting,
+        source,
+    }
+}
+
+fn initializes_and_funds_the_vault(token_program: Pubkey) {
+    let schedule = setup(token_program, params(20, None, true));
+
+    let vesting = schedule.env.vesting(&schedule.vesting).unwrap();
+    assert_eq!(vesting.total_amount, AMOUNT);
+    assert_eq!(vesting.claimed_amount, 0);
+    assert_eq!(vesting.creator, schedule.creator.pubkey());
+    assert_eq!(vesting.id, Some(1));
+    let vault = pda::vault_address(&schedule.vesting).0;
+    assert_eq!(schedule.env.token_balance(&vault), Some(AMOUNT));
+    assert_eq!(schedule.env.token_balance(&schedule.source), Some(0));
+}
+
+fn claims_what_vested_as_the_clock_moves(token_program: Pubkey) {
+    let mut schedule = setup(token_program, params(20, None, true));
+
+    // Before the start nothing has vested
+    assert_eq!(schedule.estimate(), 0);
+    let failed = schedule.claim().unwrap_err();
+    assert_eq!(
+        error_code(&failed),
+        Some(VestingError::CliffNotReached.into())
+    );
+
+    // Halfway: the 20% cliff and half of the remaining 80%
+    schedule.env.warp_to(START + 500);
+    assert_eq!(schedule.estimate(), 600_000);
+    let meta = schedule.claim().unwrap();
+    assert_eq!(schedule.beneficiary_balance(), Some(600_000));
+    let emitted = events(&meta);
+    let [VestingEvent::Claimed(event)] = emitted.as_slice() else {
+        panic!("expected one VestingClaimed event");
+    };
+    assert_eq!(event.amount, 600_000);
+    assert_eq!(event.claimed_amount, 600_000);
+    assert_eq!(event.time, START + 500);
+
+    // Claiming again at the same time finds nothing new
+    assert_eq!(schedule.estimate(), 0);
+    let failed = schedule.claim().unwrap_err();
+    assert_eq!(
+        error_code(&failed),
+        Some(VestingError::NothingToClaim.into())
+    );
+
+    // After the end everything is claimable
+    schedule.env.warp_to(END + 1);
+    assert_eq!(schedule.estimate(), 400_000);
+    schedule.claim().unwrap();
+    assert_eq!(schedule.beneficiary_balance(), Some(AMOUNT));
+    assert_eq!(
+        schedule
+            .env
+            .vesting(&schedule.vesting)
+            .unwrap()
+            .claimed_amount,
+        AMOUNT
+    );
+}
+
+fn unlocks_at_interval_boundaries(token_program: Pubkey) {
+    let mut schedule = setup(token_program, params(0, Some(250), false));
+
+    schedule.env.warp_to(START + 249);
+    assert_eq!(schedule.estimate(), 0);
+    schedule.env.warp_to(START + 250);
+    assert_eq!(schedule.estimate(), 250_000);
+    schedule.env.warp_to(START + 999);
+    assert_eq!(schedule.estimate(), 750_000);
+
+    let status: VestingStatus = schedule
+        .env
+        .view(instructions::vesting_status(&schedule.vesting, None), &[]);
+    assert_eq!(status.vested, 750_000);
+    assert_eq!(status.next_unlock_time, Some(END));
+    assert_eq!(status.next_unlock_amount, 250_000);
+}
+
+fn returns_the_claim_result(token_program: Pubkey) {
+    let mut schedule = setup(token_program, params(0, None, true));
+
+    schedule.env.warp_to(START + 100);
+    let meta = schedule.claim().unwrap();
+    let mut data = meta.return_data.data.clone();
+    data.resize(solana_sdk::program::MAX_RETURN_DATA, 0);
+    let result: ClaimResult =
+        anchor_lang::AnchorDeserialize::deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(result.amount, 100_000);
+    assert_eq!(result.claimed_amount, 100_000);
+    assert_eq!(result.remaining, 900_000);
+}
+
+fn revokes_the_unvested_remainder(token_program: Pubkey) {
+    let mut schedule = setup(token_program, params(0, None, true));
+
+    schedule.env.warp_to(START + 300);
+    schedule.claim().unwrap();
+    let meta = schedule.revoke().unwrap();
+
+    let emitted = events(&meta);
+    let [VestingEvent::Revoked(event)] = emitted.as_slice() else {
+        panic!("expected one RevokedEvent");
+    };
+    assert_eq!(event.vested, 300_000);
+    assert_eq!(event.claimed_amount, 300_000);
+    assert_eq!(event.unvested, 700_000);
+    assert_eq!(event.returned, 700_000);
+    assert_eq!(schedule.env.token_balance(&schedule.source), Some(700_000));
+    // The vault is closed and its rent refunded
+    assert!(schedule
+        .env
+        .svm
+        .get_account(&pda::vault_address(&schedule.vesting).0)
+        .is_none());
+    assert_ne!(
+        schedule.env.vesting(&schedule.vesting).unwrap().revoked_at,
+        0
+    );
+
+    // Without a vault there is nothing left to claim or revoke
+    schedule.env.warp_to(END);
+    let failed = schedule.claim().unwrap_err();
+    assert_eq!(
+        error_code(&failed),
+        Some(ErrorCode::AccountNotInitialized.into())
+    );
+    let failed = schedule.revoke().unwrap_err();
+    assert_eq!(
+        error_code(&failed),
+        Some(ErrorCode::AccountNotInitialized.into())
+    );
+}
+
+fn revokes_a_vault_holding_surplus(token_program: Pubkey) {
+    let mut schedule = setup(token_program, params(0, None, true));
+    let vault = pda::vault_address(&schedule.vesting).0;
+    schedule
+        .env
+        .mint_to(&vault, &schedule.mint, &token_program, 500);
+
+    schedule.env.warp_to(START + 300);
+    let meta = schedule.revoke().unwrap();
+    let emitted = events(&meta);
+    let [VestingEvent::Revoked(event)] = emitted.as_slice() else {
+        panic!("expected one RevokedEvent");
+    };
+    assert_eq!(event.unvested, AMOUNT);
+    assert_eq!(event.returned, AMOUNT + 500);
+    assert_eq!(
+        schedule.env.token_balance(&schedule.source),
+        Some(AMOUNT + 500)
+    );
+    assert!(schedule.env.svm.get_account(&vault).is_none());
+}
+
+fn refuses_to_revoke_irrevocable_schedules(token_program: Pubkey) {
+    let mut schedule = setup(token_program, params(0, None, false));
+
+    let failed = schedule.revoke().unwrap_err();
+    assert_eq!(error_code(&failed), Some(VestingError::NotRevocable.into()));
+    let vault = pda::vault_address(&schedule.vesting).0;
+    assert_eq!(schedule.env.token_balance(&vault), Some(AMOUNT));
+}
+
+fn rejects_invalid_schedules(token_program: Pubkey) {
+    let mut env = TestEnv::new();
+    let creator = env.funded_keypair();
+    let mint = env.create_mint(&token_program, 6);
+    env.fund(&creator.pubkey(), &mint, &token_program, AMOUNT);
+    let accounts =
+        InitializeAccounts::new(creator.pubkey(), Pubkey::new_unique(), mint, token_program);
+
+    let cases = [
+        (
+            InitializeParams {
+                end_time: START,
+                ..params(0, None, false)
+            },
+            VestingError::InvalidTimeRange,
+        ),
+        (params(101, None, false), VestingError::InvalidCliff),
+        (params(0, Some(0), false), VestingError::InvalidInterval),
+        (
+            InitializeParams {
+                name: "x".repeat(33),
+                ..params(0, None, false)
+            },
+            VestingError::InvalidName,
+        ),
+    ];
+    for (params, error) in cases {
+        let failed = env
+            .send(&[instructions::initialize(&accounts, params)], &[&creator])
+            .unwrap_err();
+        assert_eq!(error_code(&failed), Some(error.into()));
+    }
+}
+
+fn rejects_claims_signed_by_others(token_program: Pubkey) {
+    let mut schedule = setup(token_program, params(0, None, true));
+    schedule.env.warp_to(END);
+
+    let impostor = schedule.env.funded_keypair();
+    // The real schedule, claimed and signed by another key
+    let instruction = instructions::claim(
+        &impostor.pubkey(),
+        &schedule.vesting,
+        &schedule.mint,
+        &schedule.token_program,
+    );
+    let failed = schedule.env.send(&[instruction], &[&impostor]).unwrap_err();
+    assert_eq!(
+        error_code(&failed),
+        Some(ErrorCode::ConstraintHasOne.into())
+    );
+    assert_eq!(schedule.beneficiary_balance(), None);
+}
+
+fn sweeps_only_the_surplus(token_program: Pubkey) {
+    let mut schedule = setup(token_program, params(0, None, true));
+    let vault = pda::vault_address(&schedule.vesting).0;
+    assert_eq!(schedule.reconcile().surplus, 0);
+    let creator = schedule.creator.insecure_clone();
+    let failed = schedule.sweep_surplus(&creator).unwrap_err();
+    assert_eq!(error_code(&failed), Some(VestingError::NoSurplus.into()));
+
+    // Tokens sent straight to the vault, on top of the schedule
+    schedule
+        .env
+        .mint_to(&vault, &schedule.mint, &token_program, 500);
+    schedule.env.warp_to(START + 500);
+    schedule.claim().unwrap();
+    assert_eq!(
+        schedule.reconcile(),
+        Reconciliation {
+            vault_balance: AMOUNT / 2 + 500,
+            owed: AMOUNT / 2,
+            surplus: 500,
+            shortfall: 0,
+        }
+    );
+
+    let beneficiary = schedule.beneficiary.insecure_clone();
+    let failed = schedule.sweep_surplus(&beneficiary).unwrap_err();
+    assert_eq!(
+        error_code(&failed),
+        Some(ErrorCode::ConstraintHasOne.into())
+    );
+
+    let meta = schedule.sweep_surplus(&creator).unwrap();
+    let emitted = events(&meta);
+    let [VestingEvent::SurplusSwept(event)] = emitted.as_slice() else {
+        panic!("expected one SurplusSwept event");
+    };
+    assert_eq!((event.amount, event.owed), (500, AMOUNT / 2));
+    assert_eq!(schedule.env.token_balance(&schedule.source), Some(500));
+    assert_eq!(schedule.env.token_balance(&vault), Some(AMOUNT / 2));
+
+    // The beneficiary still gets the whole grant
+    schedule.env.warp_to(END);
+    schedule.claim().unwrap();
+    assert_eq!(schedule.beneficiary_balance(), Some(AMOUNT));
+}
+
+fn rescues_tokens_of_other_mints(token_program: Pubkey) {
+    let mut schedule = setup(token_program, params(0, None, true));
+    let foreign_mint = schedule.env.create_mint(&token_program, 9);
+    // Sent to the vesting PDA's associated token account by mistake
+    let stuck = schedule
+        .env
+        .fund(&schedule.vesting, &foreign_mint, &token_program, 777);
+    let creator = schedule.creator.insecure_clone();
+    let beneficiary = schedule.beneficiary.insecure_clone();
+    let destination = schedule
+        .env
+        .fund(&beneficiary.pubkey(), &foreign_mint, &token_program, 0);
+    let rescue = |signer: &Keypair, token_account: &Pubkey, mint: &Pubkey, destination: &Pubkey| {
+        instructions::rescue_tokens(
+            &signer.pubkey(),
+            &schedule.vesting,
+            token_account,
+            destination,
+            mint,
+            &token_program,
+        )
+    };
+
+    let impostor = schedule.env.funded_keypair();
+    let instruction = rescue(&impostor, &stuck, &foreign_mint, &destination);
+    let failed = schedule.env.send(&[instruction], &[&impostor]).unwrap_err();
+    assert_eq!(
+        error_code(&failed),
+        Some(VestingError::RescueNotAllowed.into())
+    );
+
+    // The vault holds the vested mint and stays out of reach
+    let vault = pda::vault_address(&schedule.vesting).0;
+    let instruction = rescue(&creator, &vault, &schedule.mint, &schedule.source);
+    let failed = schedule.env.send(&[instruction], &[&creator]).unwrap_err();
+    assert_eq!(
+        error_code(&failed),
+        Some(VestingError::CannotRescueVestedMint.into())
+    );
+
+    let instruction = instructions::set_rescue_policy(
+        &creator.pubkey(),
+        &beneficiary.pubkey(),
+        &schedule.vesting,
+        RescuePolicy::BeneficiaryOnly,
+    );
+    schedule
+        .env
+        .send(&[instruction], &[&creator, &beneficiary])
+        .unwrap();
+    let instruction = rescue(&creator, &stuck, &foreign_mint, &destination);
+    let failed = schedule.env.send(&[instruction], &[&creator]).unwrap_err();
+    assert_eq!(
+        error_code(&failed),
+        Some(VestingError::RescueNotAllowed.into())
+    );
+
+    let instruction = rescue(&beneficiary, &stuck, &foreign_mint, &destination);
+    let meta = schedule.env.send(&[instruction], &[&beneficiary]).unwrap();
+    let emitted = events(&meta);
+    let [VestingEvent::TokensRescued(event)] = emitted.as_slice() else {
+        panic!("expected one TokensRescued event");
+    };
+    assert_eq!((event.mint, event.amount), (foreign_mint, 777));
+    assert_eq!(schedule.env.token_balance(&destination), Some(777));
+    assert_eq!(schedule.env.token_balance(&stuck), None);
+    assert_eq!(schedule.env.token_balance(&vault), Some(AMOUNT));
+}
+
+/// Runs every scenario as one test per token program
+macro_rules! token_program_tests {
+    ($($scenario:ident),* $(,)?) => {
+        mod spl_token {
+            $(#[test] fn $scenario() { super::$scenario(anchor_spl::token::ID) })*
+        }
+        mod token_2022 {
+            $(#[test] fn $scenario() { super::$scenario(anchor_spl::token_2022::ID) })*
+        }
+    };
+}
+
+token_program_tests!(
+    initializes_and_funds_the_vault,
+    claims_what_vested_as_the_clock_moves,
+    unlocks_at_interval_boundaries,
+    returns_the_claim_result,
+    revokes_the_unvested_remainder,
+    revokes_a_vault_holding_surplus,
+    refuses_to_revoke_irrevocable_schedules,
+    rejects_invalid_schedules,
+    rejects_claims_signed_by_others,
+    sweeps_only_the_surplus,
+    rescues_tokens_of_other_mints,
+);
@@ -2,3 +2,49 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+/// Seed prefix of `Vesting` accounts, followed by the creator, beneficiary, mint and ID
+#[constant]
+pub const VESTING_SEED: &[u8] = b"vesting";
+
+/// Seed prefix of `VestingZeroCopy` accounts, followed by the creator, beneficiary, mint and ID
+#[constant]
+pub const ZERO_COPY_VESTING_SEED: &[u8] = b"vesting_zc";
+
+/// Seed prefix of vaults, followed by the vesting account
+#[constant]
+pub const VAULT_SEED: &[u8] = b"vault";
+
+/// Seed of the singleton `Config` account
+#[constant]
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// Seed prefix of `AllowedMint` accounts, followed by the mint
+#[constant]
+pub const ALLOWED_MINT_SEED: &[u8] = b"allowed_mint";
+
+/// Seed prefix of `FeeWaiver` accounts, followed by the creator
+#[constant]
+pub const FEE_WAIVER_SEED: &[u8] = b"fee_waiver";
+
+/// Seed prefix of `ScheduleIndex` pages, followed by the kind, owner and page number
+#[constant]
+pub const SCHEDULE_INDEX_SEED: &[u8] = b"schedule_index";
+
+/// Number of vesting accounts one `ScheduleIndex` page holds
+#[constant]
+pub const SCHEDULE_INDEX_PAGE_CAPACITY: u32 = 64;
+
+/// Largest fee in basis points, 100%
+#[constant]
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+/// Maximum number of unlock events returned by one `unlock_calendar` call,
+/// keeps the page within the return data limit
+#[constant]
+pub const UNLOCK_CALENDAR_PAGE_SIZE: u32 = 32;
+
+/// Schema version of `VestingInitialized`, `VestingClaimed` and `RevokedEvent`,
+/// bumped whenever their fields change
+#[constant]
+pub const EVENT_SCHEMA_VERSION: u8 = 2;
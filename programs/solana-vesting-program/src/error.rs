@@ -22,4 +22,54 @@ pub enum VestingError {
     TokenMintMismatch,
     #[msg("Vesting has already been revoked.")]
     AlreadyRevoked,
+    #[msg("Vesting is already paused.")]
+    AlreadyPaused,
+    #[msg("Vesting is not paused.")]
+    NotPaused,
+    #[msg("Vesting has already ended.")]
+    VestingEnded,
+    #[msg("Amendment would reduce the amount already vested.")]
+    VestedAmountReduced,
+    #[msg("Acceleration must make the schedule more favorable to the beneficiary.")]
+    InvalidAcceleration,
+    #[msg("Vesting account layout version is not supported.")]
+    UnknownAccountVersion,
+    #[msg("Vesting account is already on the latest layout.")]
+    AlreadyMigrated,
+    #[msg("Name must be at most 32 bytes and contain no null bytes.")]
+    InvalidName,
+    #[msg("Signer is not allowed to perform this action.")]
+    Unauthorized,
+    #[msg("Program is paused.")]
+    ProgramPaused,
+    #[msg("Mint is not on the allowlist.")]
+    MintNotAllowed,
+    #[msg("Fee basis points must be at most 10000.")]
+    InvalidFee,
+    #[msg("A fee is charged but the treasury account to receive it is missing.")]
+    MissingTreasury,
+    #[msg("Treasury account does not match the program config.")]
+    InvalidTreasury,
+    #[msg("Schedule index page is full, use the next page.")]
+    IndexPageFull,
+    #[msg("Schedule index does not belong to this creator or beneficiary.")]
+    IndexMismatch,
+    #[msg("Vesting account is not listed in this schedule index.")]
+    NotInIndex,
+    #[msg("Vesting must be fully claimed or revoked before it can be closed.")]
+    VestingNotFinished,
+    #[msg("Token balances do not add up after the transfer, invariant check failed.")]
+    InvariantViolation,
+    #[msg("The vault holds nothing beyond what the schedule owes.")]
+    NoSurplus,
+    #[msg("Tokens of the vested mint cannot be rescued.")]
+    CannotRescueVestedMint,
+    #[msg("The schedule's rescue policy does not allow this signer to rescue tokens.")]
+    RescueNotAllowed,
+    #[msg("Vesting account still exists, pass the index to `close` instead.")]
+    ScheduleStillOpen,
+    #[msg("Schedule index page still lists schedules.")]
+    IndexNotEmpty,
+    #[msg("Signer is not the proposed admin.")]
+    NotPendingAdmin,
 }
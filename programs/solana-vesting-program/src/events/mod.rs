@@ -1,7 +1,37 @@
+mod surplus_swept;
+mod tokens_rescued;
+mod vesting_accelerated;
+mod vesting_amended;
 mod vesting_claimed;
+mod vesting_closed;
 mod vesting_initialized;
+mod vesting_paused;
+mod vesting_resumed;
 mod vesting_revoked;
 
+pub use surplus_swept::*;
+pub use tokens_rescued::*;
+pub use vesting_accelerated::*;
+pub use vesting_amended::*;
 pub use vesting_claimed::*;
+pub use vesting_closed::*;
 pub use vesting_initialized::*;
+pub use vesting_paused::*;
+pub use vesting_resumed::*;
 pub use vesting_revoked::*;
+
+/// Emits an event through a self-CPI when built with the `event-cpi` feature,
+/// so it survives log truncation, and to the program logs otherwise. The
+/// instruction's accounts need `#[cfg_attr(feature = "event-cpi", event_cpi)]`.
+macro_rules! emit_event {
+    ($ctx:ident, $event:expr) => {{
+        #[cfg(feature = "event-cpi")]
+        {
+            let ctx = &$ctx;
+            anchor_lang::prelude::emit_cpi!($event);
+        }
+        #[cfg(not(feature = "event-cpi"))]
+        anchor_lang::prelude::emit!($event);
+    }};
+}
+pub(crate) use emit_event;
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct SurplusSwept {
+    pub vesting: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    /// Tokens moved out of the vault, beyond what the schedule owes
+    pub amount: u64,
+    /// What the vault still holds for the beneficiary
+    pub owed: u64,
+    pub timestamp: i64,
+}
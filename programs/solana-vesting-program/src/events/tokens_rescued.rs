@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct TokensRescued {
+    pub vesting: Pubkey,
+    /// Creator or beneficiary who rescued the tokens, received the account's rent
+    pub authority: Pubkey,
+    /// Mint of the rescued tokens, never the vested one
+    pub mint: Pubkey,
+    /// The vesting PDA's token account that was emptied and closed
+    pub token_account: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
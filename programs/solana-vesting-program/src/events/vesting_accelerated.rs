@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::Acceleration;
+
+#[event]
+pub struct VestingAccelerated {
+    /// The vesting account that was accelerated
+    pub vesting: Pubkey,
+    /// What the creator asked for
+    pub acceleration: Acceleration,
+    /// Amount vested just before the acceleration
+    pub vested_before: u64,
+    /// Amount vested right after the acceleration
+    pub vested_after: u64,
+    /// End time of the schedule after the acceleration
+    pub end_time: i64,
+    /// When the acceleration occurred
+    pub timestamp: i64,
+}
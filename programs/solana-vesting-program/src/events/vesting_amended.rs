@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// The schedule parameters that `amend_schedule` is allowed to change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ScheduleParams {
+    pub end_time: i64,
+    pub payment_interval: i64,
+    pub cliff_percentage: u8,
+    pub revocable: bool,
+}
+
+#[event]
+pub struct VestingAmended {
+    /// The vesting account that was amended
+    pub vesting: Pubkey,
+    /// Parameters before the amendment
+    pub before: ScheduleParams,
+    /// Parameters after the amendment
+    pub after: ScheduleParams,
+    /// Amount vested at the time of the amendment under the old schedule
+    pub vested_before: u64,
+    /// Amount vested at the time of the amendment under the new schedule, never lower
+    pub vested_after: u64,
+    /// When the amendment occurred
+    pub timestamp: i64,
+}
@@ -2,7 +2,14 @@ use anchor_lang::prelude::*;
 
 #[event]
 pub struct VestingClaimed {
+    /// See `EVENT_SCHEMA_VERSION`
+    pub schema_version: u8,
     pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    /// Amount transferred by this claim
     pub amount: u64,
+    /// Total claimed so far, including `amount`
+    pub claimed_amount: u64,
     pub time: i64,
 }
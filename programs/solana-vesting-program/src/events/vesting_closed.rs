@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct VestingClosed {
+    /// The vesting account that was closed
+    pub vesting: Pubkey,
+    /// Who received the rent back
+    pub creator: Pubkey,
+    /// When the account was closed
+    pub timestamp: i64,
+}
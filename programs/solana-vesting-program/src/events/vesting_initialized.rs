@@ -2,8 +2,24 @@ use anchor_lang::prelude::*;
 
 #[event]
 pub struct VestingInitialized {
+    /// See `EVENT_SCHEMA_VERSION`
+    pub schema_version: u8,
     pub vesting: Pubkey,
     pub beneficiary: Pubkey,
     pub creator: Pubkey,
+    pub mint: Pubkey,
+    /// ID the vesting address is derived from
+    pub id: u64,
     pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub cliff_percentage: u8,
+    /// Seconds between unlocks, 0 for a linear schedule
+    pub payment_interval: i64,
+    pub name: String,
+    pub revocable: bool,
+    /// Protocol fee paid in lamports
+    pub fee_lamports: u64,
+    /// Protocol fee paid in the vested token, on top of `total_amount`
+    pub fee_amount: u64,
 }
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct VestingPaused {
+    /// The vesting account that was paused
+    pub vesting: Pubkey,
+    /// When the pause window started
+    pub paused_at: i64,
+}
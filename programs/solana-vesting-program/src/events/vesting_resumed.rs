@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct VestingResumed {
+    /// The vesting account that was resumed
+    pub vesting: Pubkey,
+    /// When the pause window started
+    pub paused_at: i64,
+    /// When the pause window ended
+    pub resumed_at: i64,
+    /// Total seconds the schedule has been delayed by pauses, this window included
+    pub paused_duration: i64,
+    /// End time after shifting it by the paused duration
+    pub effective_end_time: i64,
+}
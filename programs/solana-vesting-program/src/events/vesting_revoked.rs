@@ -2,10 +2,24 @@ use anchor_lang::prelude::*;
 
 #[event]
 pub struct RevokedEvent {
+    /// See `EVENT_SCHEMA_VERSION`
+    pub schema_version: u8,
     /// The vesting account that was revoked
     pub vesting: Pubkey,
-    /// Amount of tokens returned to the creator
+    /// Who revoked the schedule
+    pub creator: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    /// Amount vested at the time of the revocation
+    pub vested: u64,
+    /// Amount the beneficiary had claimed before the revocation
+    pub claimed_amount: u64,
+    /// Amount still owed to the beneficiary when the schedule was revoked,
+    /// `total_amount - claimed_amount`
     pub unvested: u64,
+    /// Amount of tokens returned to the creator: the whole vault, so `unvested`
+    /// plus any surplus, or less if the vault was short
+    pub returned: u64,
     /// When the revocation occurred
     pub timestamp: i64,
 }
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::{events::VestingAccelerated, state::Vesting, utils};
+
+/// Ways the creator can speed up a schedule. Each of them can only move
+/// the vested amount up at any point in time.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum Acceleration {
+    /// Vest everything that is left right away
+    Full,
+    /// Move the end of the schedule to an earlier time
+    EndTime { end_time: i64 },
+    /// Move the whole curve, start and end, earlier by `seconds`
+    Shift { seconds: i64 },
+    /// Vest `amount` right away on top of the schedule
+    Bonus { amount: u64 },
+}
+
+#[derive(Accounts)]
+pub struct Accelerate<'info> {
+    #[account(mut, has_one = creator)]
+    pub vesting: Account<'info, Vesting>,
+
+    /// Authority allowed to accelerate (the original creator)
+    pub creator: Signer<'info>,
+}
+
+pub fn accelerate_handler(ctx: Context<Accelerate>, acceleration: Acceleration) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vesting = &mut ctx.accounts.vesting;
+
+    require!(vesting.revoked_at == 0, VestingError::VestingRevoked);
+
+    let vested_before = utils::calculate_vested_amount(vesting, now)?;
+
+    match acceleration {
+        Acceleration::Full => {
+            vesting.accelerated_amount = vesting.total_amount;
+        }
+        Acceleration::EndTime { end_time } => {
+            require!(
+                end_time < vesting.end_time,
+                VestingError::InvalidAcceleration
+            );
+            require!(
+                end_time > vesting.start_time,
+                VestingError::InvalidTimeRange
+            );
+            vesting.end_time = end_time;
+        }
+        Acceleration::Shift { seconds } => {
+            require!(seconds > 0, VestingError::InvalidAcceleration);
+            vesting.start_time = vesting
+                .start_time
+                .checked_sub(seconds)
+                .ok_or(VestingError::MathOverflow)?;
+            vesting.end_time = vesting
+                .end_time
+                .checked_sub(seconds)
+                .ok_or(VestingError::MathOverflow)?;
+        }
+        Acceleration::Bonus { amount } => {
+            require!(amount > 0, VestingError::InvalidAcceleration);
+            vesting.accelerated_amount = vesting
+                .accelerated_amount
+                .checked_add(amount)
+                .ok_or(VestingError::MathOverflow)?
+                .min(vesting.total_amount);
+        }
+    }
+
+    let vested_after = utils::calculate_vested_amount(vesting, now)?;
+    require!(
+        vested_after >= vested_before,
+        VestingError::InvalidAcceleration
+    );
+
+    emit!(VestingAccelerated {
+        vesting: vesting.key(),
+        acceleration,
+        vested_before,
+        vested_after,
+        end_time: vesting.end_time,
+        timestamp: now,
+    });
+
+    Ok(())
+}
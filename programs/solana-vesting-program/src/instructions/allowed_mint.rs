@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{AllowedMint, Config, ALLOWED_MINT_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct AddAllowedMint<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        seeds = [ALLOWED_MINT_SEED, mint.key().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + AllowedMint::INIT_SPACE,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAllowedMint<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [ALLOWED_MINT_SEED, allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn add_allowed_mint_handler(ctx: Context<AddAllowedMint>) -> Result<()> {
+    let allowed_mint = &mut ctx.accounts.allowed_mint;
+    allowed_mint.mint = ctx.accounts.mint.key();
+    allowed_mint.bump = ctx.bumps.allowed_mint;
+    Ok(())
+}
+
+pub fn remove_allowed_mint_handler(_ctx: Context<RemoveAllowedMint>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::{events::VestingAmended, state::Vesting, utils};
+
+#[derive(Accounts)]
+pub struct AmendSchedule<'info> {
+    #[account(mut, has_one = creator, has_one = beneficiary)]
+    pub vesting: Account<'info, Vesting>,
+
+    /// Both parties have to consent to an amendment
+    pub creator: Signer<'info>,
+    pub beneficiary: Signer<'info>,
+}
+
+pub fn amend_schedule_handler(
+    ctx: Context<AmendSchedule>,
+    end_time: i64,
+    cliff_percentage: u8,
+    payment_interval: Option<i64>,
+    revocable: bool,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vesting = &mut ctx.accounts.vesting;
+
+    require!(vesting.revoked_at == 0, VestingError::VestingRevoked);
+    utils::validate_schedule(
+        vesting.start_time,
+        end_time,
+        cliff_percentage,
+        payment_interval,
+    )?;
+
+    let before = vesting.schedule_params();
+    let vested_before = utils::calculate_vested_amount(vesting, now)?;
+
+    vesting.end_time = end_time;
+    vesting.cliff_percentage = cliff_percentage;
+    vesting.payment_interval = payment_interval.unwrap_or(0);
+    vesting.revocable = revocable;
+
+    // Never take back what has already vested, whatever the beneficiary signs
+    let vested_after = utils::calculate_vested_amount(vesting, now)?;
+    require!(
+        vested_after >= vested_before,
+        VestingError::VestedAmountReduced
+    );
+
+    emit!(VestingAmended {
+        vesting: vesting.key(),
+        before,
+        after: vesting.schedule_params(),
+        vested_before,
+        vested_after,
+        timestamp: now,
+    });
+
+    Ok(())
+}
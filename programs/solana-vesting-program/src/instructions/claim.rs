@@ -5,14 +5,19 @@ use anchor_spl::{
     token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
-use crate::{error::*, events::VestingClaimed, utils, Vesting};
+use crate::{
+    error::*,
+    events::{emit_event, VestingClaimed},
+    utils, ClaimResult, Config, Vesting, CONFIG_SEED, EVENT_SCHEMA_VERSION, VAULT_SEED,
+};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct Claim<'info> {
     #[account(mut,  has_one = beneficiary)]
     pub vesting: Account<'info, Vesting>,
 
-    #[account(mut, seeds = [b"vault", vesting.key().as_ref()], bump,
+    #[account(mut, seeds = [VAULT_SEED, vesting.key().as_ref()], bump,
         token::authority = vesting,
         token::mint = mint,
         token::token_program = token_program,)]
@@ -34,6 +39,9 @@ pub struct Claim<'info> {
     pub beneficiary: Signer<'info>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
 }
 
 impl Claim<'_> {
@@ -45,33 +53,27 @@ impl Claim<'_> {
             authority: self.vesting.to_account_info(),
         };
 
-        let signer_seeds: &[&[u8]] = &[
-            b"vesting",
-            self.beneficiary.key.as_ref(),
-            self.vault.mint.as_ref(),
-            self.vesting.name.as_bytes(),
-            // Add other seeds used to derive the vesting PDA
-            &[self.vesting.bump],
-        ];
-        let s = &[signer_seeds];
-        let cpi_ctx =
-            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, s);
-        token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)
+        self.vesting.with_signer_seeds(|s| {
+            let cpi_ctx =
+                CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, s);
+            token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)
+        })
     }
 }
 
-pub fn claim_handler(ctx: Context<Claim>) -> Result<()> {
+pub fn claim_handler(ctx: Context<Claim>) -> Result<ClaimResult> {
+    ctx.accounts.config.check_not_paused()?;
+
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
     let vesting = &mut ctx.accounts.vesting;
 
     require!(vesting.revoked_at == 0, VestingError::VestingRevoked);
 
-    if now < vesting.start_time {
+    let claimable = utils::calculate_claimable_amount(vesting, now)?;
+    if claimable == 0 && utils::schedule_time(vesting, now) < vesting.start_time {
         return Err(error!(VestingError::CliffNotReached));
     }
-
-    let claimable = utils::calculate_claimable_amount(vesting, now)?;
     require!(claimable > 0, VestingError::NothingToClaim);
 
     vesting.claimed_amount = vesting
@@ -80,12 +82,30 @@ pub fn claim_handler(ctx: Context<Claim>) -> Result<()> {
         .ok_or(VestingError::MathOverflow)?;
     vesting.last_claimed_at = now;
     ctx.accounts.claim_tokens(claimable)?;
-
-    emit!(VestingClaimed {
-        vesting: ctx.accounts.vesting.key(),
+    #[cfg(feature = "invariant-checks")]
+    crate::invariants::check_vault_backs_schedule(
+        &ctx.accounts.vault.to_account_info(),
+        ctx.accounts.vesting.total_amount,
+        ctx.accounts.vesting.claimed_amount,
+    )?;
+
+    let vesting = &ctx.accounts.vesting;
+    emit_event!(
+        ctx,
+        VestingClaimed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vesting: vesting.key(),
+            beneficiary: vesting.beneficiary,
+            mint: vesting.mint,
+            amount: claimable,
+            claimed_amount: vesting.claimed_amount,
+            time: now,
+        }
+    );
+
+    Ok(ClaimResult {
         amount: claimable,
-        time: now,
-    });
-
-    Ok(())
+        claimed_amount: vesting.claimed_amount,
+        remaining: ctx.accounts.vault.amount.saturating_sub(claimable),
+    })
 }
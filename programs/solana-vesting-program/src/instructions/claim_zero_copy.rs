@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::TransferChecked,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::*,
+    events::{emit_event, VestingClaimed},
+    utils, ClaimResult, Config, VestingZeroCopy, CONFIG_SEED, EVENT_SCHEMA_VERSION, VAULT_SEED,
+    ZERO_COPY_VESTING_SEED,
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimZeroCopy<'info> {
+    #[account(mut, has_one = beneficiary)]
+    pub vesting: AccountLoader<'info, VestingZeroCopy>,
+
+    #[account(mut, seeds = [VAULT_SEED, vesting.key().as_ref()], bump,
+        token::authority = vesting,
+        token::mint = mint,
+        token::token_program = token_program,)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::authority = beneficiary,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,)]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+impl ClaimZeroCopy<'_> {
+    fn claim_tokens(&self, amount: u64, creator: Pubkey, id: u64, bump: u8) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            to: self.beneficiary_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vesting.to_account_info(),
+        };
+
+        let signer_seeds: &[&[u8]] = &[
+            ZERO_COPY_VESTING_SEED,
+            creator.as_ref(),
+            self.beneficiary.key.as_ref(),
+            self.vault.mint.as_ref(),
+            &id.to_le_bytes(),
+            &[bump],
+        ];
+        let s = &[signer_seeds];
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, s);
+        token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+}
+
+pub fn claim_zero_copy_handler(ctx: Context<ClaimZeroCopy>) -> Result<ClaimResult> {
+    ctx.accounts.config.check_not_paused()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut vesting = ctx.accounts.vesting.load_mut()?;
+
+    require!(vesting.revoked_at == 0, VestingError::VestingRevoked);
+
+    let claimable = utils::calculate_claimable_amount(&vesting, now)?;
+    if claimable == 0 && utils::schedule_time(&vesting, now) < vesting.start_time {
+        return Err(error!(VestingError::CliffNotReached));
+    }
+    require!(claimable > 0, VestingError::NothingToClaim);
+
+    vesting.claimed_amount = vesting
+        .claimed_amount
+        .checked_add(claimable)
+        .ok_or(VestingError::MathOverflow)?;
+    vesting.last_claimed_at = now;
+    let (creator, id, bump) = (vesting.creator, vesting.id, vesting.bump);
+    let (beneficiary, mint, claimed_amount) =
+        (vesting.beneficiary, vesting.mint, vesting.claimed_amount);
+    #[cfg(feature = "invariant-checks")]
+    let total_amount = vesting.total_amount;
+    drop(vesting);
+    ctx.accounts.claim_tokens(claimable, creator, id, bump)?;
+    #[cfg(feature = "invariant-checks")]
+    crate::invariants::check_vault_backs_schedule(
+        &ctx.accounts.vault.to_account_info(),
+        total_amount,
+        claimed_amount,
+    )?;
+
+    emit_event!(
+        ctx,
+        VestingClaimed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vesting: ctx.accounts.vesting.key(),
+            beneficiary,
+            mint,
+            amount: claimable,
+            claimed_amount,
+            time: now,
+        }
+    );
+
+    Ok(ClaimResult {
+        amount: claimable,
+        claimed_amount,
+        remaining: ctx.accounts.vault.amount.saturating_sub(claimable),
+    })
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, TokenInterface};
+
+use crate::error::*;
+use crate::{events::VestingClosed, state::*, VAULT_SEED};
+
+#[derive(Accounts)]
+pub struct Close<'info> {
+    #[account(mut, has_one = creator, close = creator)]
+    pub vesting: Account<'info, Vesting>,
+
+    /// CHECK: The vault, already closed if the schedule was revoked, closed here otherwise
+    #[account(mut, seeds = [VAULT_SEED, vesting.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut,
+        constraint = creator_index.kind == IndexKind::Creator
+            && creator_index.owner == vesting.creator @ VestingError::IndexMismatch)]
+    pub creator_index: Option<Account<'info, ScheduleIndex>>,
+
+    #[account(mut,
+        constraint = beneficiary_index.kind == IndexKind::Beneficiary
+            && beneficiary_index.owner == vesting.beneficiary @ VestingError::IndexMismatch)]
+    pub beneficiary_index: Option<Account<'info, ScheduleIndex>>,
+
+    /// Receives the rent of the vesting account, its vault and its index entries
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl Close<'_> {
+    fn close_vault(&self) -> Result<()> {
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.creator.to_account_info(),
+            authority: self.vesting.to_account_info(),
+        };
+        self.vesting.with_signer_seeds(|signer_seeds| {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token_interface::close_account(cpi_ctx)
+        })
+    }
+}
+
+pub fn close_handler(ctx: Context<Close>) -> Result<()> {
+    let vesting = &ctx.accounts.vesting;
+    require!(
+        vesting.revoked_at != 0 || vesting.claimed_amount >= vesting.total_amount,
+        VestingError::VestingNotFinished
+    );
+
+    // A revoked schedule had its vault closed by `revoke`
+    if !ctx.accounts.vault.data_is_empty() {
+        ctx.accounts.close_vault()?;
+    }
+
+    let vesting_key = ctx.accounts.vesting.key();
+    let creator = ctx.accounts.creator.to_account_info();
+    if let Some(index) = ctx.accounts.creator_index.as_mut() {
+        index.remove(&vesting_key)?;
+        shrink_to_fit(index, &creator)?;
+    }
+    if let Some(index) = ctx.accounts.beneficiary_index.as_mut() {
+        index.remove(&vesting_key)?;
+        shrink_to_fit(index, &creator)?;
+    }
+
+    emit!(VestingClosed {
+        vesting: vesting_key,
+        creator: ctx.accounts.creator.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{IndexKind, ScheduleIndex, SCHEDULE_INDEX_SEED};
+
+#[derive(Accounts)]
+#[instruction(kind: IndexKind, page: u32)]
+pub struct CreateScheduleIndex<'info> {
+    #[account(
+        init,
+        seeds = [SCHEDULE_INDEX_SEED, &[kind as u8], owner.key().as_ref(), &page.to_le_bytes()],
+        bump,
+        payer = owner,
+        space = ScheduleIndex::space(0),
+    )]
+    pub schedule_index: Account<'info, ScheduleIndex>,
+
+    /// Creator or beneficiary whose schedules the page lists, pays the rent of the empty page
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_schedule_index_handler(
+    ctx: Context<CreateScheduleIndex>,
+    kind: IndexKind,
+    page: u32,
+) -> Result<()> {
+    let schedule_index = &mut ctx.accounts.schedule_index;
+    schedule_index.kind = kind;
+    schedule_index.owner = ctx.accounts.owner.key();
+    schedule_index.page = page;
+    schedule_index.bump = ctx.bumps.schedule_index;
+    schedule_index.schedules = Vec::new();
+    Ok(())
+}
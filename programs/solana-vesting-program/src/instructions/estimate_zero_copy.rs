@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+use crate::VestingZeroCopy;
+
+#[derive(Accounts)]
+pub struct ReadOnlyClaimZeroCopy<'info> {
+    pub vesting: AccountLoader<'info, VestingZeroCopy>,
+    pub signer: Signer<'info>,
+}
+
+pub fn estimate_zero_copy_handler(ctx: Context<ReadOnlyClaimZeroCopy>) -> Result<u64> {
+    let vesting = ctx.accounts.vesting.load()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    crate::utils::calculate_claimable_amount(&vesting, now)
+}
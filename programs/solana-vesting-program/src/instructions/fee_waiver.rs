@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, FeeWaiver, CONFIG_SEED, FEE_WAIVER_SEED};
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct AddFeeWaiver<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        seeds = [FEE_WAIVER_SEED, creator.as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + FeeWaiver::INIT_SPACE,
+    )]
+    pub fee_waiver: Account<'info, FeeWaiver>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveFeeWaiver<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [FEE_WAIVER_SEED, fee_waiver.creator.as_ref()],
+        bump = fee_waiver.bump,
+    )]
+    pub fee_waiver: Account<'info, FeeWaiver>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn add_fee_waiver_handler(ctx: Context<AddFeeWaiver>, creator: Pubkey) -> Result<()> {
+    let fee_waiver = &mut ctx.accounts.fee_waiver;
+    fee_waiver.creator = creator;
+    fee_waiver.bump = ctx.bumps.fee_waiver;
+    Ok(())
+}
+
+pub fn remove_fee_waiver_handler(_ctx: Context<RemoveFeeWaiver>) -> Result<()> {
+    Ok(())
+}
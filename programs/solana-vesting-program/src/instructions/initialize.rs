@@ -1,15 +1,20 @@
 use crate::error::*;
 use crate::events::*;
 use crate::state::*;
-use anchor_lang::prelude::*;
+use crate::utils;
+use crate::{
+    ALLOWED_MINT_SEED, CONFIG_SEED, EVENT_SCHEMA_VERSION, FEE_WAIVER_SEED, VAULT_SEED, VESTING_SEED,
+};
+use anchor_lang::{prelude::*, system_program};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
-#[instruction(amount: u64, start_time: i64, end_time: i64, cliff_percentage: u8, payment_interval: Option<i64>, name: String, revocable: bool)]
+#[instruction(id: u64)]
 pub struct InitializeVesting<'info> {
     #[account(
         init,
-        seeds = [b"vesting", beneficiary.key().as_ref(), mint.key().as_ref(),name.as_bytes()],
+        seeds = [VESTING_SEED, authority.key().as_ref(), beneficiary.key().as_ref(), mint.key().as_ref(), id.to_le_bytes().as_ref()],
         bump,
         payer = payer,
         space = 8 + Vesting::INIT_SPACE,
@@ -18,7 +23,7 @@ pub struct InitializeVesting<'info> {
 
     #[account(
         init,
-        seeds = [b"vault", vesting.key().as_ref()],
+        seeds = [VAULT_SEED, vesting.key().as_ref()],
         bump,
         token::mint = mint,
         token::authority = vesting,
@@ -28,10 +33,16 @@ pub struct InitializeVesting<'info> {
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// Pays the rent of the new accounts and the lamport fee
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    #[account(mut, token::mint = mint, token::authority = payer, token::token_program = token_program,)]
+    /// Owner of `source_token_account`, becomes the creator of the schedule.
+    /// Usually the same key as `payer`, or a PDA of a program creating the
+    /// schedule through CPI.
+    pub authority: Signer<'info>,
+
+    #[account(mut, token::mint = mint, token::authority = authority, token::token_program = token_program,)]
     pub source_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Only used as a key
@@ -40,10 +51,55 @@ pub struct InitializeVesting<'info> {
 
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Only has to exist while the mint allowlist is enabled, see `Config::check_mint_allowed`
+    #[account(seeds = [ALLOWED_MINT_SEED, mint.key().as_ref()], bump)]
+    pub allowed_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the lamport fee, only needed when one is charged
+    #[account(mut, address = config.treasury @ VestingError::InvalidTreasury)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// Receives the token fee, only needed when one is charged
+    #[account(mut,
+        token::mint = mint,
+        token::authority = config.treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Only has to exist for creators exempt from fees, see `Config::creation_fees`
+    #[account(seeds = [FEE_WAIVER_SEED, authority.key().as_ref()], bump)]
+    pub fee_waiver: UncheckedAccount<'info>,
+
+    /// Index page of the creator's schedules to list the new vesting account in,
+    /// grown by one entry paid for by `payer`
+    #[account(mut,
+        realloc = ScheduleIndex::space(creator_index.schedules.len() + 1),
+        realloc::payer = payer,
+        realloc::zero = false,
+        constraint = creator_index.kind == IndexKind::Creator
+            && creator_index.owner == authority.key() @ VestingError::IndexMismatch)]
+    pub creator_index: Option<Account<'info, ScheduleIndex>>,
+
+    /// Index page of the beneficiary's schedules to list the new vesting account in,
+    /// only exists if the beneficiary created it. Grown by one entry paid for by
+    /// `payer`; the beneficiary can remove it with `remove_schedule_index_entries`.
+    #[account(mut,
+        realloc = ScheduleIndex::space(beneficiary_index.schedules.len() + 1),
+        realloc::payer = payer,
+        realloc::zero = false,
+        constraint = beneficiary_index.kind == IndexKind::Beneficiary
+            && beneficiary_index.owner == beneficiary.key() @ VestingError::IndexMismatch)]
+    pub beneficiary_index: Option<Account<'info, ScheduleIndex>>,
 }
 #[allow(clippy::too_many_arguments)]
 pub fn initialize_handler(
     ctx: Context<InitializeVesting>,
+    id: u64,
     amount: u64,
     start_time: i64,
     end_time: i64,
@@ -52,17 +108,18 @@ pub fn initialize_handler(
     name: String,
     revocable: bool,
 ) -> Result<()> {
-    require!(end_time > start_time, VestingError::InvalidTimeRange);
-    require!(cliff_percentage <= 100, VestingError::InvalidCliff);
-    if let Some(interval) = payment_interval {
-        require!(interval > 0, VestingError::InvalidInterval);
-    }
+    ctx.accounts.config.check_not_paused()?;
+    ctx.accounts
+        .config
+        .check_mint_allowed(&ctx.accounts.allowed_mint)?;
+    utils::validate_schedule(start_time, end_time, cliff_percentage, payment_interval)?;
+    utils::validate_name(&name)?;
 
     let vesting_key = ctx.accounts.vesting.key();
 
     let vesting = &mut ctx.accounts.vesting;
     vesting.beneficiary = ctx.accounts.beneficiary.key();
-    vesting.creator = ctx.accounts.payer.key();
+    vesting.creator = ctx.accounts.authority.key();
     vesting.mint = ctx.accounts.mint.key();
     vesting.start_time = start_time;
     vesting.end_time = end_time;
@@ -75,22 +132,115 @@ pub fn initialize_handler(
     vesting.revoked_at = 0;
     vesting.last_claimed_at = 0;
     vesting.bump = ctx.bumps.vesting;
+    vesting.paused_at = 0;
+    vesting.paused_duration = 0;
+    vesting.accelerated_amount = 0;
+    vesting.version = VESTING_VERSION;
+    vesting.id = Some(id);
+    vesting.rescue_policy = RescuePolicy::default();
+    vesting.reserved = [0; VESTING_RESERVED_LEN];
 
     let cpi_accounts = TransferChecked {
         from: ctx.accounts.source_token_account.to_account_info(),
         to: ctx.accounts.vault.to_account_info(),
         mint: ctx.accounts.mint.to_account_info(),
-        authority: ctx.accounts.payer.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
     };
     let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
     anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+    #[cfg(feature = "invariant-checks")]
+    crate::invariants::check_vault_backs_schedule(
+        &ctx.accounts.vault.to_account_info(),
+        amount,
+        0,
+    )?;
 
-    emit!(VestingInitialized {
-        vesting: vesting_key,
-        beneficiary: vesting.beneficiary,
-        creator: vesting.creator,
-        total_amount: amount,
-    });
+    let (fee_lamports, fee_amount) = charge_creation_fees(
+        &ctx.accounts.config,
+        &ctx.accounts.fee_waiver,
+        amount,
+        &ctx.accounts.payer,
+        &ctx.accounts.authority,
+        &ctx.accounts.source_token_account,
+        &ctx.accounts.mint,
+        ctx.accounts.treasury.as_ref(),
+        ctx.accounts.treasury_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
+
+    if let Some(index) = ctx.accounts.creator_index.as_mut() {
+        index.append(vesting_key)?;
+    }
+    if let Some(index) = ctx.accounts.beneficiary_index.as_mut() {
+        index.append(vesting_key)?;
+    }
+
+    let vesting = &ctx.accounts.vesting;
+    emit_event!(
+        ctx,
+        VestingInitialized {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vesting: vesting_key,
+            beneficiary: vesting.beneficiary,
+            creator: vesting.creator,
+            mint: vesting.mint,
+            id,
+            total_amount: amount,
+            start_time,
+            end_time,
+            cliff_percentage,
+            payment_interval: vesting.payment_interval,
+            name: vesting.name.clone(),
+            revocable,
+            fee_lamports,
+            fee_amount,
+        }
+    );
 
     Ok(())
 }
+
+/// Sends the protocol fees to the treasury, on top of the vested `amount`: the
+/// lamport fee from `payer` and the token fee from `source_token_account`,
+/// owned by `authority`. Returns the lamport and token fees that were charged.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn charge_creation_fees<'info>(
+    config: &Config,
+    fee_waiver: &AccountInfo<'info>,
+    amount: u64,
+    payer: &Signer<'info>,
+    authority: &Signer<'info>,
+    source_token_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    treasury: Option<&UncheckedAccount<'info>>,
+    treasury_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Interface<'info, TokenInterface>,
+    system_program: &Program<'info, System>,
+) -> Result<(u64, u64)> {
+    let (fee_lamports, fee_amount) = config.creation_fees(amount, fee_waiver)?;
+
+    if fee_lamports > 0 {
+        let treasury = treasury.ok_or(VestingError::MissingTreasury)?;
+        let cpi_accounts = system_program::Transfer {
+            from: payer.to_account_info(),
+            to: treasury.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, fee_lamports)?;
+    }
+
+    if fee_amount > 0 {
+        let treasury_token_account = treasury_token_account.ok_or(VestingError::MissingTreasury)?;
+        let cpi_accounts = TransferChecked {
+            from: source_token_account.to_account_info(),
+            to: treasury_token_account.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        anchor_spl::token_interface::transfer_checked(cpi_ctx, fee_amount, mint.decimals)?;
+    }
+
+    Ok((fee_lamports, fee_amount))
+}
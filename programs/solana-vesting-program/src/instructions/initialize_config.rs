@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::program::SolanaVestingProgram;
+use crate::{Config, CONFIG_RESERVED_LEN, CONFIG_SEED, CONFIG_VERSION};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        seeds = [CONFIG_SEED],
+        bump,
+        payer = authority,
+        space = 8 + Config::INIT_SPACE,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Must be the program's upgrade authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, SolanaVestingProgram>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ VestingError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_config_handler(ctx: Context<InitializeConfig>, admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = admin;
+    config.paused = false;
+    config.fee_lamports = 0;
+    config.fee_bps = 0;
+    config.treasury = admin;
+    config.mint_allowlist_enabled = false;
+    config.bump = ctx.bumps.config;
+    config.pending_admin = None;
+    config.version = CONFIG_VERSION;
+    config.reserved = [0; CONFIG_RESERVED_LEN];
+
+    Ok(())
+}
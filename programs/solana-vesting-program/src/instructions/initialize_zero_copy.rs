@@ -0,0 +1,168 @@
+use crate::error::*;
+use crate::events::*;
+use crate::instructions::charge_creation_fees;
+use crate::state::*;
+use crate::utils;
+use crate::{
+    ALLOWED_MINT_SEED, CONFIG_SEED, EVENT_SCHEMA_VERSION, FEE_WAIVER_SEED, VAULT_SEED,
+    ZERO_COPY_VESTING_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct InitializeVestingZeroCopy<'info> {
+    #[account(
+        init,
+        seeds = [ZERO_COPY_VESTING_SEED, authority.key().as_ref(), beneficiary.key().as_ref(), mint.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + VestingZeroCopy::LEN,
+    )]
+    pub vesting: AccountLoader<'info, VestingZeroCopy>,
+
+    #[account(
+        init,
+        seeds = [VAULT_SEED, vesting.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting,
+        token::token_program = token_program,
+
+        payer = payer,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pays the rent of the new accounts and the lamport fee
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Owner of `source_token_account`, becomes the creator of the schedule,
+    /// like in `InitializeVesting`
+    pub authority: Signer<'info>,
+
+    #[account(mut, token::mint = mint, token::authority = authority, token::token_program = token_program,)]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Only used as a key
+    pub beneficiary: AccountInfo<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Only has to exist while the mint allowlist is enabled, see `Config::check_mint_allowed`
+    #[account(seeds = [ALLOWED_MINT_SEED, mint.key().as_ref()], bump)]
+    pub allowed_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the lamport fee, only needed when one is charged
+    #[account(mut, address = config.treasury @ VestingError::InvalidTreasury)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// Receives the token fee, only needed when one is charged
+    #[account(mut,
+        token::mint = mint,
+        token::authority = config.treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Only has to exist for creators exempt from fees, see `Config::creation_fees`
+    #[account(seeds = [FEE_WAIVER_SEED, authority.key().as_ref()], bump)]
+    pub fee_waiver: UncheckedAccount<'info>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_zero_copy_handler(
+    ctx: Context<InitializeVestingZeroCopy>,
+    id: u64,
+    amount: u64,
+    start_time: i64,
+    end_time: i64,
+    cliff_percentage: u8,
+    payment_interval: Option<i64>,
+    name: String,
+    revocable: bool,
+) -> Result<()> {
+    ctx.accounts.config.check_not_paused()?;
+    ctx.accounts
+        .config
+        .check_mint_allowed(&ctx.accounts.allowed_mint)?;
+    utils::validate_schedule(start_time, end_time, cliff_percentage, payment_interval)?;
+
+    let vesting_key = ctx.accounts.vesting.key();
+
+    let mut vesting = ctx.accounts.vesting.load_init()?;
+    vesting.beneficiary = ctx.accounts.beneficiary.key();
+    vesting.creator = ctx.accounts.authority.key();
+    vesting.mint = ctx.accounts.mint.key();
+    vesting.id = id;
+    vesting.start_time = start_time;
+    vesting.end_time = end_time;
+    vesting.total_amount = amount;
+    vesting.cliff_percentage = cliff_percentage;
+    vesting.payment_interval = payment_interval.unwrap_or(0);
+    vesting.set_name(&name)?;
+    vesting.revocable = revocable as u8;
+    vesting.bump = ctx.bumps.vesting;
+    vesting.version = VESTING_VERSION;
+    let (beneficiary, creator) = (vesting.beneficiary, vesting.creator);
+    drop(vesting);
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.source_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+    #[cfg(feature = "invariant-checks")]
+    crate::invariants::check_vault_backs_schedule(
+        &ctx.accounts.vault.to_account_info(),
+        amount,
+        0,
+    )?;
+
+    let (fee_lamports, fee_amount) = charge_creation_fees(
+        &ctx.accounts.config,
+        &ctx.accounts.fee_waiver,
+        amount,
+        &ctx.accounts.payer,
+        &ctx.accounts.authority,
+        &ctx.accounts.source_token_account,
+        &ctx.accounts.mint,
+        ctx.accounts.treasury.as_ref(),
+        ctx.accounts.treasury_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit_event!(
+        ctx,
+        VestingInitialized {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vesting: vesting_key,
+            beneficiary,
+            creator,
+            mint: ctx.accounts.mint.key(),
+            id,
+            total_amount: amount,
+            start_time,
+            end_time,
+            cliff_percentage,
+            payment_interval: payment_interval.unwrap_or(0),
+            name,
+            revocable,
+            fee_lamports,
+            fee_amount,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::error::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct MigrateVesting<'info> {
+    /// CHECK: Read by hand as an old layout does not deserialize into `Account<Vesting>`
+    #[account(mut, owner = crate::ID)]
+    pub vesting: UncheckedAccount<'info>,
+
+    /// Covers the rent for the extra space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_vesting_handler(ctx: Context<MigrateVesting>) -> Result<()> {
+    let vesting_info = ctx.accounts.vesting.to_account_info();
+    let (vesting, needs_migration) = read_vesting(&vesting_info.try_borrow_data()?)?;
+    require!(needs_migration, VestingError::AlreadyMigrated);
+
+    let new_len = 8 + Vesting::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(new_len);
+    let top_up = rent.saturating_sub(vesting_info.lamports());
+    if top_up > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: vesting_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, top_up)?;
+    }
+
+    vesting_info.realloc(new_len, true)?;
+    let mut data = vesting_info.try_borrow_mut_data()?;
+    vesting.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
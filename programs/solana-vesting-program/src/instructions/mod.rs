@@ -1,9 +1,53 @@
+pub mod accelerate;
+pub mod allowed_mint;
+pub mod amend_schedule;
 pub mod claim;
+pub mod claim_zero_copy;
+pub mod close;
+pub mod create_schedule_index;
 pub mod estimate_claimable;
+pub mod estimate_zero_copy;
+pub mod fee_waiver;
 pub mod initialize;
+pub mod initialize_config;
+pub mod initialize_zero_copy;
+pub mod migrate_vesting;
+pub mod pause;
+pub mod prune_schedule_index;
+pub mod reconcile;
+pub mod rescue_tokens;
+pub mod resume;
 pub mod revoke;
+pub mod revoke_zero_copy;
+pub mod set_rescue_policy;
+pub mod sweep_surplus;
+pub mod unlock_calendar;
+pub mod update_config;
+pub mod vesting_status;
 
+pub use accelerate::*;
+pub use allowed_mint::*;
+pub use amend_schedule::*;
 pub use claim::*;
+pub use claim_zero_copy::*;
+pub use close::*;
+pub use create_schedule_index::*;
 pub use estimate_claimable::*;
+pub use estimate_zero_copy::*;
+pub use fee_waiver::*;
 pub use initialize::*;
+pub use initialize_config::*;
+pub use initialize_zero_copy::*;
+pub use migrate_vesting::*;
+pub use pause::*;
+pub use prune_schedule_index::*;
+pub use reconcile::*;
+pub use rescue_tokens::*;
+pub use resume::*;
 pub use revoke::*;
+pub use revoke_zero_copy::*;
+pub use set_rescue_policy::*;
+pub use sweep_surplus::*;
+pub use unlock_calendar::*;
+pub use update_config::*;
+pub use vesting_status::*;
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::{events::VestingPaused, state::Vesting, utils};
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(mut, has_one = creator)]
+    pub vesting: Account<'info, Vesting>,
+
+    /// Authority allowed to pause (the original creator)
+    pub creator: Signer<'info>,
+}
+
+pub fn pause_handler(ctx: Context<Pause>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vesting = &mut ctx.accounts.vesting;
+
+    // Pausing takes time away from the beneficiary, so it needs the same consent as revoking
+    require!(vesting.revocable, VestingError::NotRevocable);
+    require!(vesting.revoked_at == 0, VestingError::VestingRevoked);
+    require!(vesting.paused_at == 0, VestingError::AlreadyPaused);
+    require!(
+        now < utils::effective_end_time(vesting, now),
+        VestingError::VestingEnded
+    );
+
+    vesting.paused_at = now;
+
+    emit!(VestingPaused {
+        vesting: vesting.key(),
+        paused_at: now,
+    });
+
+    Ok(())
+}
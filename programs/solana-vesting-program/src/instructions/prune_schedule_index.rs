@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::{shrink_to_fit, ScheduleIndex};
+
+#[derive(Accounts)]
+pub struct PruneScheduleIndex<'info> {
+    #[account(mut, has_one = owner)]
+    pub schedule_index: Account<'info, ScheduleIndex>,
+
+    /// CHECK: Owner of the page, gets the rent of the removed entry
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: A vesting account listed in the page that no longer exists
+    #[account(constraint = vesting.data_is_empty() @ VestingError::ScheduleStillOpen)]
+    pub vesting: UncheckedAccount<'info>,
+}
+
+/// Removes a schedule closed without passing this page to `close`. Anyone can
+/// call it, the entry is dead either way.
+pub fn prune_schedule_index_handler(ctx: Context<PruneScheduleIndex>) -> Result<()> {
+    let schedule_index = &mut ctx.accounts.schedule_index;
+    schedule_index.remove(&ctx.accounts.vesting.key())?;
+    shrink_to_fit(schedule_index, &ctx.accounts.owner)
+}
+
+#[derive(Accounts)]
+pub struct RemoveScheduleIndexEntries<'info> {
+    #[account(mut, has_one = owner)]
+    pub schedule_index: Account<'info, ScheduleIndex>,
+
+    /// Owner of the page, gets the rent of the removed entries
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Removes `schedules` from one of the owner's pages, whether or not they are
+/// still open. Lets a beneficiary drop schedules others listed in their page.
+pub fn remove_schedule_index_entries_handler(
+    ctx: Context<RemoveScheduleIndexEntries>,
+    schedules: Vec<Pubkey>,
+) -> Result<()> {
+    let schedule_index = &mut ctx.accounts.schedule_index;
+    for vesting in &schedules {
+        schedule_index.remove(vesting)?;
+    }
+    shrink_to_fit(schedule_index, &ctx.accounts.owner)
+}
+
+#[derive(Accounts)]
+pub struct CloseScheduleIndex<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        close = owner,
+        constraint = schedule_index.schedules.is_empty() @ VestingError::IndexNotEmpty,
+    )]
+    pub schedule_index: Account<'info, ScheduleIndex>,
+
+    /// Paid for the page when creating it, gets its rent back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn close_schedule_index_handler(_ctx: Context<CloseScheduleIndex>) -> Result<()> {
+    Ok(())
+}
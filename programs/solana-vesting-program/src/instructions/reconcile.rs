@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{utils, Reconciliation, Vesting, VAULT_SEED};
+
+#[derive(Accounts)]
+pub struct ReadOnlyReconcile<'info> {
+    #[account()]
+    pub vesting: Account<'info, Vesting>,
+
+    #[account(seeds = [VAULT_SEED, vesting.key().as_ref()], bump, token::authority = vesting)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn reconcile_handler(ctx: Context<ReadOnlyReconcile>) -> Result<Reconciliation> {
+    Ok(utils::reconcile(
+        &ctx.accounts.vesting,
+        ctx.accounts.vault.amount,
+    ))
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::TransferChecked,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::error::*;
+use crate::{
+    events::{emit_event, TokensRescued},
+    state::*,
+    CONFIG_SEED,
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    pub vesting: Account<'info, Vesting>,
+
+    /// Any token account of the vesting PDA in another mint than the vested one
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vesting,
+        token::token_program = token_program,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Where the rescued tokens go
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = mint.key() != vesting.mint @ VestingError::CannotRescueVestedMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Creator or beneficiary, as the schedule's `rescue_policy` allows. Receives
+    /// the rent of the closed token account.
+    #[account(
+        mut,
+        constraint = vesting.rescue_policy.allows(&vesting, authority.key) @ VestingError::RescueNotAllowed
+    )]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+impl RescueTokens<'_> {
+    fn transfer_tokens(&self, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.token_account.to_account_info(),
+            to: self.destination.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vesting.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+
+    fn close_token_account(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_accounts = CloseAccount {
+            account: self.token_account.to_account_info(),
+            destination: self.authority.to_account_info(),
+            authority: self.vesting.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::close_account(cpi_ctx)
+    }
+}
+
+/// Empties a token account the vesting PDA holds in a foreign mint into
+/// `destination` and closes it. The vault and any other account of the vested
+/// mint are out of reach.
+pub fn rescue_tokens_handler(ctx: Context<RescueTokens>) -> Result<u64> {
+    ctx.accounts.config.check_not_paused()?;
+
+    let amount = ctx.accounts.token_account.amount;
+    ctx.accounts.vesting.with_signer_seeds(|signer_seeds| {
+        if amount > 0 {
+            ctx.accounts.transfer_tokens(amount, signer_seeds)?;
+        }
+        ctx.accounts.close_token_account(signer_seeds)
+    })?;
+
+    emit_event!(
+        ctx,
+        TokensRescued {
+            vesting: ctx.accounts.vesting.key(),
+            authority: ctx.accounts.authority.key(),
+            mint: ctx.accounts.mint.key(),
+            token_account: ctx.accounts.token_account.key(),
+            destination: ctx.accounts.destination.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(amount)
+}
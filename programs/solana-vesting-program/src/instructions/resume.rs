@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::{events::VestingResumed, state::Vesting, utils};
+
+#[derive(Accounts)]
+pub struct Resume<'info> {
+    #[account(mut, has_one = creator)]
+    pub vesting: Account<'info, Vesting>,
+
+    /// Authority allowed to resume (the original creator)
+    pub creator: Signer<'info>,
+}
+
+pub fn resume_handler(ctx: Context<Resume>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vesting = &mut ctx.accounts.vesting;
+
+    require!(vesting.revoked_at == 0, VestingError::VestingRevoked);
+    require!(vesting.paused_at != 0, VestingError::NotPaused);
+
+    let paused_at = vesting.paused_at;
+    // Only the part of the pause after the schedule started delays it
+    let window = utils::paused_window(vesting, now);
+    vesting.paused_duration = vesting
+        .paused_duration
+        .checked_add(window)
+        .ok_or(VestingError::MathOverflow)?;
+    vesting.paused_at = 0;
+
+    emit!(VestingResumed {
+        vesting: vesting.key(),
+        paused_at,
+        resumed_at: now,
+        paused_duration: vesting.paused_duration,
+        effective_end_time: utils::effective_end_time(vesting, now),
+    });
+
+    Ok(())
+}
@@ -5,15 +5,20 @@ use anchor_spl::{
 };
 
 use crate::error::*;
-use crate::{events::RevokedEvent, state::Vesting};
+use crate::{
+    events::{emit_event, RevokedEvent},
+    state::*,
+    utils, CONFIG_SEED, EVENT_SCHEMA_VERSION, VAULT_SEED,
+};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct Revoke<'info> {
     #[account(mut, has_one = creator)]
     pub vesting: Account<'info, Vesting>,
     #[account(
         mut,
-        seeds = [b"vault", vesting.key().as_ref()],
+        seeds = [VAULT_SEED, vesting.key().as_ref()],
         bump,
         token::mint = mint,
         token::authority = vesting,
@@ -29,7 +34,8 @@ pub struct Revoke<'info> {
     )]
     pub recipient_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// Authority allowed to revoke (the original creator)
+    /// Authority allowed to revoke (the original creator), receives the vault's rent
+    #[account(mut)]
     pub creator: Signer<'info>,
     #[account()]
     /// CHECKED:
@@ -37,9 +43,12 @@ pub struct Revoke<'info> {
     pub mint: InterfaceAccount<'info, Mint>,
 
     pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
 }
 impl Revoke<'_> {
-    fn revoke_tokens(&self, unvested: u64, signer_seeds: &[&[&[u8]]; 1]) -> Result<()> {
+    fn revoke_tokens(&self, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
         let cpi_accounts = TransferChecked {
             from: self.vault.to_account_info(),
             to: self.recipient_account.to_account_info(),
@@ -52,10 +61,10 @@ impl Revoke<'_> {
             signer_seeds,
         );
 
-        token_interface::transfer_checked(cpi_context, unvested, self.mint.decimals)
+        token_interface::transfer_checked(cpi_context, amount, self.mint.decimals)
     }
 
-    fn close_ata(&self, signer_seeds: &[&[&[u8]]; 1]) -> Result<()> {
+    fn close_ata(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
         let cpi_accounts = CloseAccount {
             account: self.vault.to_account_info(),
             destination: self.creator.to_account_info(),
@@ -70,7 +79,9 @@ impl Revoke<'_> {
     }
 }
 
-pub fn revoke_handler(ctx: Context<Revoke>) -> Result<()> {
+pub fn revoke_handler(ctx: Context<Revoke>) -> Result<RevokeResult> {
+    ctx.accounts.config.check_not_paused()?;
+
     let vesting = &ctx.accounts.vesting;
 
     // Only revocable schedules can be revoked
@@ -84,29 +95,49 @@ pub fn revoke_handler(ctx: Context<Revoke>) -> Result<()> {
         .checked_sub(vesting.claimed_amount)
         .ok_or(VestingError::MathOverflow)?;
 
-    // Derive vault bump for signing
-    let seeds: &[&[u8]] = &[
-        b"vesting",
-        vesting.beneficiary.as_ref(),
-        ctx.accounts.vault.mint.as_ref(),
-        vesting.name.as_bytes(),
-        // Add other seeds used to derive the vesting PDA
-        &[vesting.bump],
-    ];
-    let signer_seeds = &[seeds];
-    ctx.accounts.revoke_tokens(unvested, signer_seeds)?;
-
-    // 2️⃣ Close the vault and refund its rent to the creator
-    ctx.accounts.close_ata(signer_seeds)?;
-
-    let vesting = &mut ctx.accounts.vesting;
+    let now = Clock::get()?.unix_timestamp;
+    let vested = utils::calculate_vested_amount(vesting, now)?;
+
+    // Everything in the vault goes back: the unvested remainder and any surplus
+    // sent on top, so that the vault can be closed
+    let returned = ctx.accounts.vault.amount;
+    vesting.with_signer_seeds(|signer_seeds| {
+        ctx.accounts.revoke_tokens(returned, signer_seeds)?;
+        #[cfg(feature = "invariant-checks")]
+        crate::invariants::check_vault_emptied(&ctx.accounts.vault.to_account_info())?;
+
+        // 2️⃣ Close the vault and refund its rent to the creator
+        ctx.accounts.close_ata(signer_seeds)
+    })?;
+    #[cfg(feature = "invariant-checks")]
+    crate::invariants::check_vault_closed(
+        &ctx.accounts.vault.to_account_info(),
+        vesting.total_amount,
+        vesting.claimed_amount,
+    )?;
+
     // 3️⃣ Mark the vesting as revoked and emit event
-    vesting.revoked_at = Clock::get()?.unix_timestamp;
-    emit!(RevokedEvent {
-        vesting: vesting.key(),
-        unvested,
-        timestamp: vesting.revoked_at,
-    });
-
-    Ok(())
+    ctx.accounts.vesting.revoked_at = now;
+    let vesting = &ctx.accounts.vesting;
+    emit_event!(
+        ctx,
+        RevokedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vesting: vesting.key(),
+            creator: vesting.creator,
+            beneficiary: vesting.beneficiary,
+            mint: vesting.mint,
+            vested,
+            claimed_amount: vesting.claimed_amount,
+            unvested,
+            returned,
+            timestamp: vesting.revoked_at,
+        }
+    );
+
+    Ok(RevokeResult {
+        amount: returned,
+        claimed_amount: vesting.claimed_amount,
+        remaining: 0,
+    })
 }
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::TransferChecked,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::error::*;
+use crate::{
+    events::{emit_event, RevokedEvent},
+    state::*,
+    utils, CONFIG_SEED, EVENT_SCHEMA_VERSION, VAULT_SEED, ZERO_COPY_VESTING_SEED,
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RevokeZeroCopy<'info> {
+    #[account(mut, has_one = creator)]
+    pub vesting: AccountLoader<'info, VestingZeroCopy>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vesting.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Where unvested tokens go back to
+    #[account(mut,
+        token::mint = mint,
+        token::token_program = token_program,
+        token::authority = creator,
+    )]
+    pub recipient_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Authority allowed to revoke (the original creator), receives the vault's rent
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+impl RevokeZeroCopy<'_> {
+    fn revoke_tokens(&self, amount: u64, signer_seeds: &[&[&[u8]]; 1]) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            to: self.recipient_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vesting.to_account_info(),
+        };
+        let cpi_context = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        token_interface::transfer_checked(cpi_context, amount, self.mint.decimals)
+    }
+
+    fn close_ata(&self, signer_seeds: &[&[&[u8]]; 1]) -> Result<()> {
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.creator.to_account_info(),
+            authority: self.vesting.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::close_account(cpi_ctx)
+    }
+}
+
+pub fn revoke_zero_copy_handler(ctx: Context<RevokeZeroCopy>) -> Result<RevokeResult> {
+    ctx.accounts.config.check_not_paused()?;
+
+    let vesting = ctx.accounts.vesting.load()?;
+
+    require!(vesting.revocable != 0, VestingError::NotRevocable);
+    require!(vesting.revoked_at == 0, VestingError::AlreadyRevoked);
+
+    let unvested = vesting
+        .total_amount
+        .checked_sub(vesting.claimed_amount)
+        .ok_or(VestingError::MathOverflow)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let vested = utils::calculate_vested_amount(&vesting, now)?;
+    let claimed_amount = vesting.claimed_amount;
+    let beneficiary = vesting.beneficiary;
+    let id = vesting.id.to_le_bytes();
+    let bump = vesting.bump;
+    drop(vesting);
+
+    let seeds: &[&[u8]] = &[
+        ZERO_COPY_VESTING_SEED,
+        ctx.accounts.creator.key.as_ref(),
+        beneficiary.as_ref(),
+        ctx.accounts.vault.mint.as_ref(),
+        &id,
+        &[bump],
+    ];
+    let signer_seeds = &[seeds];
+    let returned = ctx.accounts.vault.amount;
+    ctx.accounts.revoke_tokens(returned, signer_seeds)?;
+    #[cfg(feature = "invariant-checks")]
+    crate::invariants::check_vault_emptied(&ctx.accounts.vault.to_account_info())?;
+    ctx.accounts.close_ata(signer_seeds)?;
+
+    let mut vesting = ctx.accounts.vesting.load_mut()?;
+    #[cfg(feature = "invariant-checks")]
+    crate::invariants::check_vault_closed(
+        &ctx.accounts.vault.to_account_info(),
+        vesting.total_amount,
+        vesting.claimed_amount,
+    )?;
+    vesting.revoked_at = now;
+    drop(vesting);
+    emit_event!(
+        ctx,
+        RevokedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vesting: ctx.accounts.vesting.key(),
+            creator: ctx.accounts.creator.key(),
+            beneficiary,
+            mint: ctx.accounts.mint.key(),
+            vested,
+            claimed_amount,
+            unvested,
+            returned,
+            timestamp: now,
+        }
+    );
+
+    Ok(RevokeResult {
+        amount: returned,
+        claimed_amount,
+        remaining: 0,
+    })
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{RescuePolicy, Vesting};
+
+#[derive(Accounts)]
+pub struct SetRescuePolicy<'info> {
+    #[account(mut, has_one = creator, has_one = beneficiary)]
+    pub vesting: Account<'info, Vesting>,
+
+    /// The policy decides which of them can rescue tokens, so both have to agree
+    pub creator: Signer<'info>,
+    pub beneficiary: Signer<'info>,
+}
+
+pub fn set_rescue_policy_handler(
+    ctx: Context<SetRescuePolicy>,
+    policy: RescuePolicy,
+) -> Result<()> {
+    ctx.accounts.vesting.rescue_policy = policy;
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::TransferChecked,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::error::*;
+use crate::{
+    events::{emit_event, SurplusSwept},
+    state::*,
+    utils, CONFIG_SEED, VAULT_SEED,
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SweepSurplus<'info> {
+    #[account(has_one = creator, has_one = mint)]
+    pub vesting: Account<'info, Vesting>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vesting.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Where the surplus goes
+    #[account(mut,
+        token::mint = mint,
+        token::token_program = token_program,
+        token::authority = creator,
+    )]
+    pub recipient_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Only the creator can recover what was sent on top of the schedule
+    pub creator: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+impl SweepSurplus<'_> {
+    fn sweep_tokens(&self, amount: u64) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            to: self.recipient_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vesting.to_account_info(),
+        };
+
+        self.vesting.with_signer_seeds(|s| {
+            let cpi_ctx =
+                CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, s);
+            token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)
+        })
+    }
+}
+
+/// Moves whatever the vault holds beyond `total_amount - claimed_amount` back to the
+/// creator. The beneficiary's entitlement stays in the vault.
+pub fn sweep_surplus_handler(ctx: Context<SweepSurplus>) -> Result<u64> {
+    ctx.accounts.config.check_not_paused()?;
+
+    let vesting = &ctx.accounts.vesting;
+    require!(vesting.revoked_at == 0, VestingError::VestingRevoked);
+
+    let reconciliation = utils::reconcile(vesting, ctx.accounts.vault.amount);
+    require!(reconciliation.surplus > 0, VestingError::NoSurplus);
+
+    ctx.accounts.sweep_tokens(reconciliation.surplus)?;
+    #[cfg(feature = "invariant-checks")]
+    crate::invariants::check_vault_backs_schedule(
+        &ctx.accounts.vault.to_account_info(),
+        ctx.accounts.vesting.total_amount,
+        ctx.accounts.vesting.claimed_amount,
+    )?;
+
+    emit_event!(
+        ctx,
+        SurplusSwept {
+            vesting: ctx.accounts.vesting.key(),
+            creator: ctx.accounts.creator.key(),
+            mint: ctx.accounts.mint.key(),
+            amount: reconciliation.surplus,
+            owed: reconciliation.owed,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(reconciliation.surplus)
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::*, ReadOnlyStatus, UnlockCalendarPage, UNLOCK_CALENDAR_PAGE_SIZE};
+
+pub fn unlock_calendar_handler(
+    ctx: Context<ReadOnlyStatus>,
+    from: i64,
+    to: i64,
+    step: Option<i64>,
+) -> Result<UnlockCalendarPage> {
+    require!(to >= from, VestingError::InvalidTimeRange);
+
+    let limit = UNLOCK_CALENDAR_PAGE_SIZE as usize;
+    let events = crate::utils::unlock_calendar(&ctx.accounts.vesting, from, to, step, limit)?;
+    let next_from = match events.last() {
+        Some(last) if events.len() == limit && last.time < to => Some(last.time),
+        _ => None,
+    };
+
+    Ok(UnlockCalendarPage { events, next_from })
+}
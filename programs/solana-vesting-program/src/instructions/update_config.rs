@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::{Config, CONFIG_SEED, MAX_FEE_BPS};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(pending_admin.key()) @ VestingError::NotPendingAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub pending_admin: Signer<'info>,
+}
+
+/// First step of an admin transfer, the admin only changes once `admin`
+/// accepts. Proposing again replaces the pending admin.
+pub fn propose_admin_handler(ctx: Context<UpdateConfig>, admin: Pubkey) -> Result<()> {
+    ctx.accounts.config.pending_admin = Some(admin);
+    Ok(())
+}
+
+pub fn accept_admin_handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.pending_admin.key();
+    config.pending_admin = None;
+    Ok(())
+}
+
+pub fn set_paused_handler(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+    ctx.accounts.config.paused = paused;
+    Ok(())
+}
+
+pub fn set_fees_handler(
+    ctx: Context<UpdateConfig>,
+    fee_lamports: u64,
+    fee_bps: u16,
+    treasury: Pubkey,
+) -> Result<()> {
+    require!(fee_bps <= MAX_FEE_BPS, VestingError::InvalidFee);
+
+    let config = &mut ctx.accounts.config;
+    config.fee_lamports = fee_lamports;
+    config.fee_bps = fee_bps;
+    config.treasury = treasury;
+    Ok(())
+}
+
+pub fn set_mint_allowlist_handler(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+    ctx.accounts.config.mint_allowlist_enabled = enabled;
+    Ok(())
+}
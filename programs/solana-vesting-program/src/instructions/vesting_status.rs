@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{Vesting, VestingStatus};
+
+#[derive(Accounts)]
+pub struct ReadOnlyStatus<'info> {
+    #[account()]
+    pub vesting: Account<'info, Vesting>,
+}
+
+pub fn vesting_status_handler(
+    ctx: Context<ReadOnlyStatus>,
+    at: Option<i64>,
+) -> Result<VestingStatus> {
+    let at = match at {
+        Some(at) => at,
+        None => Clock::get()?.unix_timestamp,
+    };
+
+    crate::utils::calculate_status(&ctx.accounts.vesting, at)
+}
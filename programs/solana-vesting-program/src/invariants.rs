@@ -0,0 +1,156 @@
+//! Conservation checks run after the token CPIs of a handler. Compiled in with
+//! the `invariant-checks` feature: a violation fails the transaction with
+//! `VestingError::InvariantViolation` instead of letting it land.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::error::VestingError;
+
+/// The balance of `vault` as the CPIs left it, not as it was deserialized when
+/// the instruction started
+fn vault_balance(vault: &AccountInfo) -> Result<u64> {
+    let data = vault.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/// The vault holds at least what the schedule still owes its beneficiary.
+/// Tokens sent to it on top are surplus, see `utils::reconcile`.
+pub fn check_vault_backs_schedule(
+    vault: &AccountInfo,
+    total_amount: u64,
+    claimed_amount: u64,
+) -> Result<()> {
+    require!(
+        claimed_amount <= total_amount,
+        VestingError::InvariantViolation
+    );
+    require_gte!(
+        vault_balance(vault)?,
+        total_amount - claimed_amount,
+        VestingError::InvariantViolation
+    );
+    Ok(())
+}
+
+/// Nothing is left in the vault once the unvested tokens went back
+pub fn check_vault_emptied(vault: &AccountInfo) -> Result<()> {
+    require_eq!(vault_balance(vault)?, 0, VestingError::InvariantViolation);
+    Ok(())
+}
+
+/// The vault was closed, and the schedule never paid out more than it held
+pub fn check_vault_closed(
+    vault: &AccountInfo,
+    total_amount: u64,
+    claimed_amount: u64,
+) -> Result<()> {
+    require!(
+        claimed_amount <= total_amount,
+        VestingError::InvariantViolation
+    );
+    require_eq!(vault.lamports(), 0, VestingError::InvariantViolation);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token::spl_token::{
+        self,
+        solana_program::program_pack::Pack,
+        state::{Account as SplAccount, AccountState},
+    };
+
+    fn vault_data(amount: u64) -> Vec<u8> {
+        let mut data = vec![0; SplAccount::LEN];
+        SplAccount::pack(
+            SplAccount {
+                mint: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                amount,
+                state: AccountState::Initialized,
+                ..Default::default()
+            },
+            &mut data,
+        )
+        .unwrap();
+        data
+    }
+
+    fn check_backs(amount: u64, total_amount: u64, claimed_amount: u64) -> Result<()> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 2_039_280;
+        let mut data = vault_data(amount);
+        let vault = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        check_vault_backs_schedule(&vault, total_amount, claimed_amount)
+    }
+
+    #[test]
+    fn test_vault_backing_remaining_amount_passes() {
+        assert!(check_backs(1_000, 1_000, 0).is_ok());
+        assert!(check_backs(400, 1_000, 600).is_ok());
+        assert!(check_backs(0, 1_000, 1_000).is_ok());
+    }
+
+    #[test]
+    fn test_vault_holding_surplus_passes() {
+        assert!(check_backs(1_500, 1_000, 0).is_ok());
+        assert!(check_backs(401, 1_000, 600).is_ok());
+        assert!(check_backs(5, 1_000, 1_000).is_ok());
+    }
+
+    #[test]
+    fn test_vault_mismatch_is_a_violation() {
+        for result in [check_backs(399, 1_000, 600), check_backs(0, 1_000, 1_001)] {
+            assert_eq!(
+                result.unwrap_err(),
+                error!(VestingError::InvariantViolation)
+            );
+        }
+    }
+
+    #[test]
+    fn test_vault_closed_requires_no_lamports() {
+        let key = Pubkey::new_unique();
+        let mut data = vault_data(0);
+        let mut lamports = 0;
+        let closed = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        assert!(check_vault_emptied(&closed).is_ok());
+        assert!(check_vault_closed(&closed, 1_000, 600).is_ok());
+        assert!(check_vault_closed(&closed, 1_000, 1_001).is_err());
+
+        let mut lamports = 2_039_280;
+        let mut data = vault_data(5);
+        let open = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        assert!(check_vault_emptied(&open).is_err());
+        assert!(check_vault_closed(&open, 1_000, 600).is_err());
+    }
+}
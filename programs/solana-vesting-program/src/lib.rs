@@ -1,9 +1,14 @@
 #![allow(unexpected_cfgs)]
+// `#[program]` generates the `cpi` helpers of `initialize` and
+// `initialize_zero_copy` at the crate root, out of reach of an item-level allow
+#![cfg_attr(feature = "cpi", allow(clippy::too_many_arguments))]
 
 pub mod constants;
 pub mod error;
 pub mod events;
 pub mod instructions;
+#[cfg(feature = "invariant-checks")]
+pub mod invariants;
 pub mod state;
 pub mod utils;
 
@@ -12,13 +17,12 @@ use anchor_lang::prelude::*;
 pub use constants::*;
 pub use error::*;
 pub use instructions::*;
-use solana_security_txt::security_txt;
 pub use state::*;
 
 declare_id!("B6Ten95rDWqw8MMJy6hy2GHxiQrzjKAYBtFzFkCfuwVu");
 
 #[cfg(not(feature = "no-entrypoint"))]
-security_txt! {
+solana_security_txt::security_txt! {
     name: "Lumeryn Vesting Program" ,
     project_url:  "https://github.com/Lumeryn/solana-vesting-program",
     policy:  "https://raw.githubusercontent.com/Lumeryn/solana-vesting-program/refs/heads/main/security.md",
@@ -35,6 +39,7 @@ pub mod solana_vesting_program {
     #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         ctx: Context<InitializeVesting>,
+        id: u64,
         amount: u64,
         start_time: i64,
         end_time: i64,
@@ -45,6 +50,7 @@ pub mod solana_vesting_program {
     ) -> Result<()> {
         initialize::initialize_handler(
             ctx,
+            id,
             amount,
             start_time,
             end_time,
@@ -55,15 +61,185 @@ pub mod solana_vesting_program {
         )
     }
 
-    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+    pub fn initialize_config(ctx: Context<InitializeConfig>, admin: Pubkey) -> Result<()> {
+        initialize_config::initialize_config_handler(ctx, admin)
+    }
+
+    pub fn propose_admin(ctx: Context<UpdateConfig>, admin: Pubkey) -> Result<()> {
+        update_config::propose_admin_handler(ctx, admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        update_config::accept_admin_handler(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        update_config::set_paused_handler(ctx, paused)
+    }
+
+    pub fn set_fees(
+        ctx: Context<UpdateConfig>,
+        fee_lamports: u64,
+        fee_bps: u16,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        update_config::set_fees_handler(ctx, fee_lamports, fee_bps, treasury)
+    }
+
+    pub fn set_mint_allowlist(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        update_config::set_mint_allowlist_handler(ctx, enabled)
+    }
+
+    pub fn add_allowed_mint(ctx: Context<AddAllowedMint>) -> Result<()> {
+        allowed_mint::add_allowed_mint_handler(ctx)
+    }
+
+    pub fn remove_allowed_mint(ctx: Context<RemoveAllowedMint>) -> Result<()> {
+        allowed_mint::remove_allowed_mint_handler(ctx)
+    }
+
+    pub fn add_fee_waiver(ctx: Context<AddFeeWaiver>, creator: Pubkey) -> Result<()> {
+        fee_waiver::add_fee_waiver_handler(ctx, creator)
+    }
+
+    pub fn remove_fee_waiver(ctx: Context<RemoveFeeWaiver>) -> Result<()> {
+        fee_waiver::remove_fee_waiver_handler(ctx)
+    }
+
+    pub fn create_schedule_index(
+        ctx: Context<CreateScheduleIndex>,
+        kind: IndexKind,
+        page: u32,
+    ) -> Result<()> {
+        create_schedule_index::create_schedule_index_handler(ctx, kind, page)
+    }
+
+    pub fn prune_schedule_index(ctx: Context<PruneScheduleIndex>) -> Result<()> {
+        prune_schedule_index::prune_schedule_index_handler(ctx)
+    }
+
+    pub fn remove_schedule_index_entries(
+        ctx: Context<RemoveScheduleIndexEntries>,
+        schedules: Vec<Pubkey>,
+    ) -> Result<()> {
+        prune_schedule_index::remove_schedule_index_entries_handler(ctx, schedules)
+    }
+
+    pub fn close_schedule_index(ctx: Context<CloseScheduleIndex>) -> Result<()> {
+        prune_schedule_index::close_schedule_index_handler(ctx)
+    }
+
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        close::close_handler(ctx)
+    }
+
+    pub fn claim(ctx: Context<Claim>) -> Result<ClaimResult> {
         claim::claim_handler(ctx)
     }
 
-    pub fn revoke(ctx: Context<Revoke>) -> Result<()> {
+    pub fn revoke(ctx: Context<Revoke>) -> Result<RevokeResult> {
         revoke::revoke_handler(ctx)
     }
 
+    pub fn migrate_vesting(ctx: Context<MigrateVesting>) -> Result<()> {
+        migrate_vesting::migrate_vesting_handler(ctx)
+    }
+
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        pause::pause_handler(ctx)
+    }
+
+    pub fn resume(ctx: Context<Resume>) -> Result<()> {
+        resume::resume_handler(ctx)
+    }
+
+    pub fn accelerate(ctx: Context<Accelerate>, acceleration: Acceleration) -> Result<()> {
+        accelerate::accelerate_handler(ctx, acceleration)
+    }
+
+    pub fn amend_schedule(
+        ctx: Context<AmendSchedule>,
+        end_time: i64,
+        cliff_percentage: u8,
+        payment_interval: Option<i64>,
+        revocable: bool,
+    ) -> Result<()> {
+        amend_schedule::amend_schedule_handler(
+            ctx,
+            end_time,
+            cliff_percentage,
+            payment_interval,
+            revocable,
+        )
+    }
+
     pub fn estimate(ctx: Context<ReadOnlyClaim>) -> Result<u64> {
         estimate_claimable::estimate_claimable_handler(ctx)
     }
+
+    pub fn vesting_status(ctx: Context<ReadOnlyStatus>, at: Option<i64>) -> Result<VestingStatus> {
+        vesting_status::vesting_status_handler(ctx, at)
+    }
+
+    pub fn reconcile(ctx: Context<ReadOnlyReconcile>) -> Result<Reconciliation> {
+        reconcile::reconcile_handler(ctx)
+    }
+
+    pub fn sweep_surplus(ctx: Context<SweepSurplus>) -> Result<u64> {
+        sweep_surplus::sweep_surplus_handler(ctx)
+    }
+
+    pub fn set_rescue_policy(ctx: Context<SetRescuePolicy>, policy: RescuePolicy) -> Result<()> {
+        set_rescue_policy::set_rescue_policy_handler(ctx, policy)
+    }
+
+    pub fn rescue_tokens(ctx: Context<RescueTokens>) -> Result<u64> {
+        rescue_tokens::rescue_tokens_handler(ctx)
+    }
+
+    pub fn unlock_calendar(
+        ctx: Context<ReadOnlyStatus>,
+        from: i64,
+        to: i64,
+        step: Option<i64>,
+    ) -> Result<UnlockCalendarPage> {
+        unlock_calendar::unlock_calendar_handler(ctx, from, to, step)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_zero_copy(
+        ctx: Context<InitializeVestingZeroCopy>,
+        id: u64,
+        amount: u64,
+        start_time: i64,
+        end_time: i64,
+        cliff_percentage: u8,
+        payment_interval: Option<i64>,
+        name: String,
+        revocable: bool,
+    ) -> Result<()> {
+        initialize_zero_copy::initialize_zero_copy_handler(
+            ctx,
+            id,
+            amount,
+            start_time,
+            end_time,
+            cliff_percentage,
+            payment_interval,
+            name,
+            revocable,
+        )
+    }
+
+    pub fn claim_zero_copy(ctx: Context<ClaimZeroCopy>) -> Result<ClaimResult> {
+        claim_zero_copy::claim_zero_copy_handler(ctx)
+    }
+
+    pub fn revoke_zero_copy(ctx: Context<RevokeZeroCopy>) -> Result<RevokeResult> {
+        revoke_zero_copy::revoke_zero_copy_handler(ctx)
+    }
+
+    pub fn estimate_zero_copy(ctx: Context<ReadOnlyClaimZeroCopy>) -> Result<u64> {
+        estimate_zero_copy::estimate_zero_copy_handler(ctx)
+    }
 }
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::*, MAX_FEE_BPS};
+
+/// Layout version written by `initialize_config`
+pub const CONFIG_VERSION: u8 = 1;
+/// Bytes kept free at the end of the account for future settings
+pub const CONFIG_RESERVED_LEN: usize = 64;
+
+/// Program-wide settings, a singleton PDA at `[CONFIG_SEED]`
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    /// Key allowed to change the settings below
+    pub admin: Pubkey,
+    /// Emergency switch, blocks `initialize` and every instruction moving tokens
+    /// out of a vesting PDA while set
+    pub paused: bool,
+    /// Flat fee in lamports charged when a schedule is created
+    pub fee_lamports: u64,
+    /// Fee in basis points of the vested amount, paid in the vested token
+    pub fee_bps: u16,
+    /// Where fees are sent
+    pub treasury: Pubkey,
+    /// When set, only mints with an `AllowedMint` account can be vested
+    pub mint_allowlist_enabled: bool,
+    pub bump: u8,
+    /// Admin proposed by `propose_admin`, takes over once it signs `accept_admin`
+    pub pending_admin: Option<Pubkey>,
+    pub version: u8,
+    pub reserved: [u8; CONFIG_RESERVED_LEN],
+}
+
+/// Exempts a creator from the creation fee
+#[account]
+#[derive(InitSpace)]
+pub struct FeeWaiver {
+    pub creator: Pubkey,
+    pub bump: u8,
+}
+
+/// Marks a mint as allowed while the mint allowlist is enabled
+#[account]
+#[derive(InitSpace)]
+pub struct AllowedMint {
+    pub mint: Pubkey,
+    pub bump: u8,
+}
+
+impl Config {
+    /// Fails while the emergency pause is set
+    pub fn check_not_paused(&self) -> Result<()> {
+        require!(!self.paused, VestingError::ProgramPaused);
+        Ok(())
+    }
+
+    /// Fails if the allowlist is enabled and `allowed_mint`, the PDA at
+    /// `[ALLOWED_MINT_SEED, mint]`, hasn't been created by the admin
+    pub fn check_mint_allowed(&self, allowed_mint: &AccountInfo) -> Result<()> {
+        if self.mint_allowlist_enabled {
+            require!(is_created(allowed_mint), VestingError::MintNotAllowed);
+        }
+        Ok(())
+    }
+
+    /// Lamport and token fees for vesting `amount`, nothing if `fee_waiver`,
+    /// the PDA at `[FEE_WAIVER_SEED, creator]`, has been created by the admin
+    pub fn creation_fees(&self, amount: u64, fee_waiver: &AccountInfo) -> Result<(u64, u64)> {
+        if is_created(fee_waiver) {
+            return Ok((0, 0));
+        }
+        Ok((self.fee_lamports, self.token_fee(amount)?))
+    }
+
+    /// `fee_bps` of `amount`, rounded down
+    pub fn token_fee(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(VestingError::MathOverflow)?
+            / MAX_FEE_BPS as u128;
+        u64::try_from(fee).map_err(|_| VestingError::MathOverflow.into())
+    }
+}
+
+/// Whether a program-owned marker PDA exists
+fn is_created(info: &AccountInfo) -> bool {
+    info.owner == &crate::ID && !info.data_is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(fee_lamports: u64, fee_bps: u16) -> Config {
+        Config {
+            admin: Pubkey::default(),
+            paused: false,
+            fee_lamports,
+            fee_bps,
+            treasury: Pubkey::default(),
+            mint_allowlist_enabled: false,
+            bump: 255,
+            pending_admin: None,
+            version: CONFIG_VERSION,
+            reserved: [0; CONFIG_RESERVED_LEN],
+        }
+    }
+
+    #[test]
+    fn test_token_fee() {
+        assert_eq!(config(0, 0).token_fee(1_000_000).unwrap(), 0);
+        assert_eq!(config(0, 25).token_fee(1_000_000).unwrap(), 2_500);
+        assert_eq!(config(0, 25).token_fee(399).unwrap(), 0);
+        assert_eq!(
+            config(0, MAX_FEE_BPS).token_fee(u64::MAX).unwrap(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_creation_fees_waived() {
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 1;
+        let mut data = [1u8; 8];
+        let waiver = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            config(5_000, 25).creation_fees(1_000_000, &waiver).unwrap(),
+            (0, 0)
+        );
+
+        let system = anchor_lang::system_program::ID;
+        let mut lamports = 0;
+        let mut data = [];
+        let missing = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &system,
+            false,
+            0,
+        );
+        assert_eq!(
+            config(5_000, 25)
+                .creation_fees(1_000_000, &missing)
+                .unwrap(),
+            (5_000, 2_500)
+        );
+    }
+}
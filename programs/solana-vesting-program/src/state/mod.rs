@@ -1,3 +1,19 @@
+mod config;
+mod reconciliation;
+mod schedule_index;
+mod transfer_result;
+mod unlock_calendar;
 mod vesting;
+mod vesting_status;
+mod vesting_v0;
+mod vesting_zero_copy;
 
+pub use config::*;
+pub use reconciliation::*;
+pub use schedule_index::*;
+pub use transfer_result::*;
+pub use unlock_calendar::*;
 pub use vesting::*;
+pub use vesting_status::*;
+pub use vesting_v0::*;
+pub use vesting_zero_copy::*;
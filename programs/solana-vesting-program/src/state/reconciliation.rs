@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Vault balance against what the schedule owes, returned by the `reconcile` view
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// Tokens the vault holds
+    pub vault_balance: u64,
+    /// What the schedule still owes the beneficiary, `total_amount - claimed_amount`
+    pub owed: u64,
+    /// Tokens beyond `owed`, which the creator can recover with `sweep_surplus`
+    pub surplus: u64,
+    /// Tokens missing to pay out `owed`, e.g. withheld as transfer fees
+    pub shortfall: u64,
+}
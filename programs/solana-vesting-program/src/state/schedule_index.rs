@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::*, SCHEDULE_INDEX_PAGE_CAPACITY};
+
+/// Whose schedules an index lists
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexKind {
+    Creator,
+    Beneficiary,
+}
+
+/// One page of the vesting accounts created by or for `owner`, a PDA at
+/// `[SCHEDULE_INDEX_SEED, kind, owner, page]`. Only the owner can create a
+/// page, which starts out empty; creating a beneficiary page is how a
+/// beneficiary opts in to being listed. The page grows by one entry per
+/// schedule listed in it, paid for by whoever pays for the schedule, and
+/// shrinks again as entries are removed. Once a page holds
+/// `SCHEDULE_INDEX_PAGE_CAPACITY` entries the next one has to be used.
+#[account]
+pub struct ScheduleIndex {
+    pub kind: IndexKind,
+    pub owner: Pubkey,
+    pub page: u32,
+    pub bump: u8,
+    pub schedules: Vec<Pubkey>,
+}
+
+impl ScheduleIndex {
+    /// Account size holding `len` schedules, discriminator included
+    pub const fn space(len: usize) -> usize {
+        8 + 1 + 32 + 4 + 1 + 4 + 32 * len
+    }
+
+    pub fn append(&mut self, vesting: Pubkey) -> Result<()> {
+        require!(
+            self.schedules.len() < SCHEDULE_INDEX_PAGE_CAPACITY as usize,
+            VestingError::IndexPageFull
+        );
+        self.schedules.push(vesting);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, vesting: &Pubkey) -> Result<()> {
+        let position = self
+            .schedules
+            .iter()
+            .position(|key| key == vesting)
+            .ok_or(VestingError::NotInIndex)?;
+        self.schedules.swap_remove(position);
+        Ok(())
+    }
+}
+
+/// Shrinks `index` to the entries it still holds after removals, sending the
+/// rent it no longer needs to `recipient`
+pub fn shrink_to_fit<'info>(
+    index: &Account<'info, ScheduleIndex>,
+    recipient: &AccountInfo<'info>,
+) -> Result<()> {
+    let info = index.to_account_info();
+    let space = ScheduleIndex::space(index.schedules.len());
+    let excess = info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(space));
+    **info.try_borrow_mut_lamports()? -= excess;
+    **recipient.try_borrow_mut_lamports()? += excess;
+    info.realloc(space, false)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(len: usize) -> ScheduleIndex {
+        ScheduleIndex {
+            kind: IndexKind::Creator,
+            owner: Pubkey::new_unique(),
+            page: 0,
+            bump: 255,
+            schedules: (0..len).map(|_| Pubkey::new_unique()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_space_fits_the_entries() {
+        for len in [0, 1, SCHEDULE_INDEX_PAGE_CAPACITY as usize] {
+            let mut data = Vec::new();
+            index(len).try_serialize(&mut data).unwrap();
+            assert_eq!(data.len(), ScheduleIndex::space(len));
+        }
+    }
+
+    #[test]
+    fn test_append_stops_at_capacity() {
+        let mut full = index(SCHEDULE_INDEX_PAGE_CAPACITY as usize);
+        assert_eq!(
+            full.append(Pubkey::new_unique()).unwrap_err(),
+            error!(VestingError::IndexPageFull)
+        );
+    }
+
+    #[test]
+    fn test_remove_unlisted_fails() {
+        let mut page = index(3);
+        let listed = page.schedules[0];
+        page.remove(&listed).unwrap();
+        assert_eq!(page.schedules.len(), 2);
+        assert_eq!(
+            page.remove(&listed).unwrap_err(),
+            error!(VestingError::NotInIndex)
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// Returned by `claim` through `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClaimResult {
+    /// Amount transferred to the beneficiary
+    pub amount: u64,
+    /// Total claimed so far, including `amount`
+    pub claimed_amount: u64,
+    /// Amount left in the vault
+    pub remaining: u64,
+}
+
+/// Returned by `revoke` through `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RevokeResult {
+    /// Amount returned to the creator, the unvested remainder plus any surplus
+    /// the vault held
+    pub amount: u64,
+    /// Total the beneficiary claimed before the revocation
+    pub claimed_amount: u64,
+    /// Amount left in the vault, always 0 as the vault is closed
+    pub remaining: u64,
+}
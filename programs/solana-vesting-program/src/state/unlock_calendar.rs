@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// A point in time where part of a schedule unlocks
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UnlockEvent {
+    /// When the tokens unlock
+    pub time: i64,
+    /// Amount unlocked at `time`
+    pub amount: u64,
+    /// Cumulative amount vested at `time`
+    pub vested: u64,
+}
+
+/// One page of the unlock calendar returned by the `unlock_calendar` view
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct UnlockCalendarPage {
+    pub events: Vec<UnlockEvent>,
+    /// Pass as `from` to fetch the next page, `None` once the range is exhausted
+    pub next_from: Option<i64>,
+}
@@ -1,7 +1,42 @@
 use anchor_lang::prelude::*;
 
+use crate::events::ScheduleParams;
+use crate::VESTING_SEED;
+
+/// Layout version written by `initialize` and `migrate_vesting`
+pub const VESTING_VERSION: u8 = 1;
+/// Bytes kept free at the end of the account for future fields
+pub const VESTING_RESERVED_LEN: usize = 118;
+
+/// Who may call `rescue_tokens` on a schedule. Zeroed reserved space reads as
+/// the default.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub enum RescuePolicy {
+    #[default]
+    CreatorOrBeneficiary,
+    CreatorOnly,
+    BeneficiaryOnly,
+    Disabled,
+}
+
+impl RescuePolicy {
+    /// Whether `signer` may rescue tokens from `vesting`
+    pub fn allows(&self, vesting: &Vesting, signer: &Pubkey) -> bool {
+        let creator = *signer == vesting.creator;
+        let beneficiary = *signer == vesting.beneficiary;
+        match self {
+            Self::CreatorOrBeneficiary => creator || beneficiary,
+            Self::CreatorOnly => creator,
+            Self::BeneficiaryOnly => beneficiary,
+            Self::Disabled => false,
+        }
+    }
+}
+
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Debug)]
 pub struct Vesting {
     pub beneficiary: Pubkey,
     pub creator: Pubkey,
@@ -18,4 +53,153 @@ pub struct Vesting {
     pub revoked_at: i64,
     pub last_claimed_at: i64,
     pub bump: u8,
+    /// When the current pause started, 0 while the schedule is running
+    pub paused_at: i64,
+    /// Total seconds of completed pause windows after the start, which shift the schedule
+    pub paused_duration: i64,
+    /// Amount vested immediately by acceleration, on top of the schedule
+    pub accelerated_amount: u64,
+    /// Layout version, see `VESTING_VERSION`
+    pub version: u8,
+    /// Creator-chosen ID the PDA is derived from, `None` for schedules
+    /// created with the legacy name-based seeds
+    pub id: Option<u64>,
+    /// Who may move foreign tokens out of the vesting PDA's token accounts
+    pub rescue_policy: RescuePolicy,
+    /// Zeroed space new fields are carved out of, so the account size stays the same
+    pub reserved: [u8; VESTING_RESERVED_LEN],
+}
+
+impl Vesting {
+    /// Runs `f` with the seeds the vesting PDA signs with, ID-based or legacy
+    pub fn with_signer_seeds<R>(&self, f: impl FnOnce(&[&[&[u8]]]) -> R) -> R {
+        let bump = [self.bump];
+        match self.id {
+            Some(id) => {
+                let id = id.to_le_bytes();
+                f(&[&[
+                    VESTING_SEED,
+                    self.creator.as_ref(),
+                    self.beneficiary.as_ref(),
+                    self.mint.as_ref(),
+                    &id,
+                    &bump,
+                ]])
+            }
+            None => f(&[&[
+                VESTING_SEED,
+                self.beneficiary.as_ref(),
+                self.mint.as_ref(),
+                self.name.as_bytes(),
+                &bump,
+            ]]),
+        }
+    }
+
+    pub fn schedule_params(&self) -> ScheduleParams {
+        ScheduleParams {
+            end_time: self.end_time,
+            payment_interval: self.payment_interval,
+            cliff_percentage: self.cliff_percentage,
+            revocable: self.revocable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vesting(id: Option<u64>) -> Vesting {
+        Vesting {
+            beneficiary: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            start_time: 0,
+            end_time: 0,
+            total_amount: 0,
+            claimed_amount: 0,
+            cliff_percentage: 0,
+            payment_interval: 0,
+            name: String::from("Test"),
+            revocable: false,
+            revoked_at: 0,
+            last_claimed_at: 0,
+            bump: 0,
+            paused_at: 0,
+            paused_duration: 0,
+            accelerated_amount: 0,
+            version: VESTING_VERSION,
+            id,
+            rescue_policy: RescuePolicy::default(),
+            reserved: [0; VESTING_RESERVED_LEN],
+        }
+    }
+
+    fn signer(vesting: &Vesting) -> Pubkey {
+        vesting.with_signer_seeds(|seeds| {
+            Pubkey::create_program_address(seeds[0], &crate::ID).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_id_signer_seeds_match_pda() {
+        let mut vesting = vesting(Some(42));
+        let (pda, bump) = Pubkey::find_program_address(
+            &[
+                VESTING_SEED,
+                vesting.creator.as_ref(),
+                vesting.beneficiary.as_ref(),
+                vesting.mint.as_ref(),
+                &42u64.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        vesting.bump = bump;
+        assert_eq!(signer(&vesting), pda);
+    }
+
+    #[test]
+    fn test_legacy_signer_seeds_match_pda() {
+        let mut vesting = vesting(None);
+        let (pda, bump) = Pubkey::find_program_address(
+            &[
+                VESTING_SEED,
+                vesting.beneficiary.as_ref(),
+                vesting.mint.as_ref(),
+                b"Test",
+            ],
+            &crate::ID,
+        );
+        vesting.bump = bump;
+        assert_eq!(signer(&vesting), pda);
+    }
+
+    #[test]
+    fn test_reserved_space_reads_as_legacy() {
+        // Accounts written before `id` existed have zeroes where it now lives
+        let mut data = Vec::new();
+        vesting(None).try_serialize(&mut data).unwrap();
+        data.resize(8 + Vesting::INIT_SPACE, 0);
+        let read = Vesting::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(read.id, None);
+        assert_eq!(read.rescue_policy, RescuePolicy::CreatorOrBeneficiary);
+    }
+
+    #[test]
+    fn test_rescue_policy_allows() {
+        let vesting = vesting(Some(1));
+        let (creator, beneficiary, other) =
+            (vesting.creator, vesting.beneficiary, Pubkey::new_unique());
+        let allowed = |policy: RescuePolicy| {
+            [creator, beneficiary, other].map(|signer| policy.allows(&vesting, &signer))
+        };
+        assert_eq!(
+            allowed(RescuePolicy::CreatorOrBeneficiary),
+            [true, true, false]
+        );
+        assert_eq!(allowed(RescuePolicy::CreatorOnly), [true, false, false]);
+        assert_eq!(allowed(RescuePolicy::BeneficiaryOnly), [false, true, false]);
+        assert_eq!(allowed(RescuePolicy::Disabled), [false, false, false]);
+    }
 }
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// Snapshot of a schedule at a point in time, returned by the `vesting_status` view
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct VestingStatus {
+    /// Timestamp the snapshot was taken at
+    pub at: i64,
+    /// Total amount vested so far, claimed or not
+    pub vested: u64,
+    /// Amount the beneficiary has already claimed
+    pub claimed: u64,
+    /// Amount the beneficiary could claim right now
+    pub claimable: u64,
+    /// Amount that has yet to vest
+    pub unvested: u64,
+    /// When the vested amount next goes up, `None` if it never will or the schedule is paused
+    pub next_unlock_time: Option<i64>,
+    /// How much vests at `next_unlock_time`
+    pub next_unlock_amount: u64,
+    /// End time shifted by the time spent paused
+    pub effective_end_time: i64,
+}
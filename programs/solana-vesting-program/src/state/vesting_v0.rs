@@ -0,0 +1,163 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{error::*, Vesting, VESTING_RESERVED_LEN, VESTING_VERSION};
+
+/// The original `Vesting` layout, without a version byte or reserved space.
+/// Only used to read accounts that still need `migrate_vesting`.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Debug)]
+pub struct VestingV0 {
+    pub beneficiary: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub cliff_percentage: u8,
+    pub payment_interval: i64,
+    #[max_len(32)]
+    pub name: String,
+    pub revocable: bool,
+    pub revoked_at: i64,
+    pub last_claimed_at: i64,
+    pub bump: u8,
+}
+
+impl VestingV0 {
+    /// Size of a v0 account, discriminator included
+    pub const LEN: usize = 8 + VestingV0::INIT_SPACE;
+}
+
+impl From<VestingV0> for Vesting {
+    fn from(old: VestingV0) -> Self {
+        Vesting {
+            beneficiary: old.beneficiary,
+            creator: old.creator,
+            mint: old.mint,
+            start_time: old.start_time,
+            end_time: old.end_time,
+            total_amount: old.total_amount,
+            claimed_amount: old.claimed_amount,
+            cliff_percentage: old.cliff_percentage,
+            payment_interval: old.payment_interval,
+            name: old.name,
+            revocable: old.revocable,
+            revoked_at: old.revoked_at,
+            last_claimed_at: old.last_claimed_at,
+            bump: old.bump,
+            paused_at: 0,
+            paused_duration: 0,
+            accelerated_amount: 0,
+            version: VESTING_VERSION,
+            id: None,
+            rescue_policy: Default::default(),
+            reserved: [0; VESTING_RESERVED_LEN],
+        }
+    }
+}
+
+/// Reads a `Vesting` account in any known layout, returning it in the current one.
+/// The second value tells whether the account still has to be migrated.
+pub fn read_vesting(data: &[u8]) -> Result<(Vesting, bool)> {
+    require!(
+        data.len() >= 8 && data[..8] == Vesting::DISCRIMINATOR,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    if data.len() < 8 + Vesting::INIT_SPACE {
+        let old = VestingV0::deserialize(&mut &data[8..])
+            .map_err(|_| VestingError::UnknownAccountVersion)?;
+        return Ok((old.into(), true));
+    }
+
+    let vesting = Vesting::try_deserialize(&mut &data[..])?;
+    require!(
+        vesting.version == VESTING_VERSION,
+        VestingError::UnknownAccountVersion
+    );
+    Ok((vesting, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A v0 account as written by the first deployment of the program
+    fn v0_fixture() -> Vec<u8> {
+        let mut data = Vec::with_capacity(VestingV0::LEN);
+        data.extend_from_slice(&Vesting::DISCRIMINATOR);
+        data.extend_from_slice(&[1; 32]); // beneficiary
+        data.extend_from_slice(&[2; 32]); // creator
+        data.extend_from_slice(&[3; 32]); // mint
+        data.extend_from_slice(&1_000i64.to_le_bytes()); // start_time
+        data.extend_from_slice(&2_000i64.to_le_bytes()); // end_time
+        data.extend_from_slice(&1_000_000u64.to_le_bytes()); // total_amount
+        data.extend_from_slice(&250_000u64.to_le_bytes()); // claimed_amount
+        data.push(20); // cliff_percentage
+        data.extend_from_slice(&100i64.to_le_bytes()); // payment_interval
+        data.extend_from_slice(&4u32.to_le_bytes()); // name length
+        data.extend_from_slice(b"Test"); // name
+        data.push(1); // revocable
+        data.extend_from_slice(&0i64.to_le_bytes()); // revoked_at
+        data.extend_from_slice(&1_500i64.to_le_bytes()); // last_claimed_at
+        data.push(254); // bump
+        data.resize(VestingV0::LEN, 0);
+        data
+    }
+
+    #[test]
+    fn test_v0_len() {
+        assert_eq!(VestingV0::LEN, 199);
+    }
+
+    #[test]
+    fn test_read_v0_fixture() {
+        let (vesting, needs_migration) = read_vesting(&v0_fixture()).unwrap();
+        assert!(needs_migration);
+        assert_eq!(vesting.beneficiary, Pubkey::new_from_array([1; 32]));
+        assert_eq!(vesting.creator, Pubkey::new_from_array([2; 32]));
+        assert_eq!(vesting.mint, Pubkey::new_from_array([3; 32]));
+        assert_eq!(vesting.start_time, 1_000);
+        assert_eq!(vesting.end_time, 2_000);
+        assert_eq!(vesting.total_amount, 1_000_000);
+        assert_eq!(vesting.claimed_amount, 250_000);
+        assert_eq!(vesting.cliff_percentage, 20);
+        assert_eq!(vesting.payment_interval, 100);
+        assert_eq!(vesting.name, "Test");
+        assert!(vesting.revocable);
+        assert_eq!(vesting.revoked_at, 0);
+        assert_eq!(vesting.last_claimed_at, 1_500);
+        assert_eq!(vesting.bump, 254);
+        assert_eq!(vesting.paused_at, 0);
+        assert_eq!(vesting.paused_duration, 0);
+        assert_eq!(vesting.accelerated_amount, 0);
+        assert_eq!(vesting.version, VESTING_VERSION);
+        assert_eq!(vesting.id, None);
+    }
+
+    #[test]
+    fn test_v0_does_not_deserialize_as_current() {
+        assert!(Vesting::try_deserialize(&mut &v0_fixture()[..]).is_err());
+    }
+
+    #[test]
+    fn test_migrated_roundtrip() {
+        let (vesting, _) = read_vesting(&v0_fixture()).unwrap();
+        let mut data = Vec::new();
+        vesting.try_serialize(&mut data).unwrap();
+        data.resize(8 + Vesting::INIT_SPACE, 0);
+
+        let (migrated, needs_migration) = read_vesting(&data).unwrap();
+        assert!(!needs_migration);
+        assert_eq!(migrated.total_amount, 1_000_000);
+        assert_eq!(migrated.claimed_amount, 250_000);
+        assert_eq!(migrated.name, "Test");
+    }
+
+    #[test]
+    fn test_rejects_other_accounts() {
+        let mut data = v0_fixture();
+        data[0] ^= 0xff;
+        assert!(read_vesting(&data).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+
+/// Maximum length of a zero-copy schedule name in bytes
+pub const ZERO_COPY_NAME_LEN: usize = 32;
+
+/// Zero-copy twin of `Vesting`, loaded through `AccountLoader` so large
+/// schedules don't pay for Borsh on every instruction. The vesting math in
+/// `utils` reads it in place through `utils::Schedule`. Only the `*_zero_copy`
+/// instructions operate on it: there is no pause, acceleration, amendment,
+/// sweep or rescue of zero-copy schedules. Fields are ordered by alignment to
+/// keep the layout free of implicit padding.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct VestingZeroCopy {
+    pub beneficiary: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    /// Creator-chosen ID the PDA is derived from
+    pub id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub payment_interval: i64,
+    pub revoked_at: i64,
+    pub last_claimed_at: i64,
+    pub paused_at: i64,
+    pub paused_duration: i64,
+    pub accelerated_amount: u64,
+    /// UTF-8 name, zero padded
+    pub name: [u8; ZERO_COPY_NAME_LEN],
+    pub cliff_percentage: u8,
+    /// 0 or 1, `bool` isn't `Pod`
+    pub revocable: u8,
+    pub bump: u8,
+    pub version: u8,
+    pub padding: [u8; 4],
+}
+
+impl VestingZeroCopy {
+    pub const LEN: usize = std::mem::size_of::<VestingZeroCopy>();
+
+    /// The name without its zero padding
+    pub fn name_bytes(&self) -> &[u8] {
+        let len = self
+            .name
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(ZERO_COPY_NAME_LEN);
+        &self.name[..len]
+    }
+
+    pub fn set_name(&mut self, name: &str) -> Result<()> {
+        let bytes = name.as_bytes();
+        require!(
+            bytes.len() <= ZERO_COPY_NAME_LEN && !bytes.contains(&0),
+            VestingError::InvalidName
+        );
+        self.name = [0; ZERO_COPY_NAME_LEN];
+        self.name[..bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::__private::bytemuck;
+
+    #[test]
+    fn test_layout_has_no_padding() {
+        assert_eq!(VestingZeroCopy::LEN % 8, 0);
+        assert_eq!(VestingZeroCopy::LEN, 96 + 11 * 8 + 32 + 8);
+    }
+
+    #[test]
+    fn test_name_roundtrip() {
+        let mut vesting: VestingZeroCopy = bytemuck::Zeroable::zeroed();
+        vesting.set_name("Team 2025").unwrap();
+        assert_eq!(vesting.name_bytes(), b"Team 2025");
+
+        vesting.set_name(&"x".repeat(ZERO_COPY_NAME_LEN)).unwrap();
+        assert_eq!(vesting.name_bytes().len(), ZERO_COPY_NAME_LEN);
+
+        assert!(vesting
+            .set_name(&"x".repeat(ZERO_COPY_NAME_LEN + 1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_math_reads_zero_copy_fields() {
+        let mut vesting: VestingZeroCopy = bytemuck::Zeroable::zeroed();
+        vesting.start_time = 1000;
+        vesting.end_time = 2000;
+        vesting.total_amount = 1000;
+        vesting.cliff_percentage = 10;
+        vesting.payment_interval = 100;
+        assert_eq!(
+            crate::utils::calculate_claimable_amount(&vesting, 1600).unwrap(),
+            640
+        );
+    }
+}
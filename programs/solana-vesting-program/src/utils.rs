@@ -1,71 +1,361 @@
 use anchor_lang::prelude::*;
 
-use crate::{Vesting, VestingError};
+use crate::{Reconciliation, UnlockEvent, Vesting, VestingError, VestingStatus, VestingZeroCopy};
+
+/// Schedule fields the vesting math reads, so it runs on a `VestingZeroCopy`
+/// in place as well as on a `Vesting`
+pub trait Schedule {
+    fn start_time(&self) -> i64;
+    fn end_time(&self) -> i64;
+    fn total_amount(&self) -> u64;
+    fn claimed_amount(&self) -> u64;
+    fn cliff_percentage(&self) -> u8;
+    /// Seconds between unlocks, 0 for a linear schedule
+    fn payment_interval(&self) -> i64;
+    fn revoked_at(&self) -> i64;
+    fn paused_at(&self) -> i64;
+    fn paused_duration(&self) -> i64;
+    fn accelerated_amount(&self) -> u64;
+}
+
+macro_rules! impl_schedule {
+    ($account:ty) => {
+        impl Schedule for $account {
+            fn start_time(&self) -> i64 {
+                self.start_time
+            }
+            fn end_time(&self) -> i64 {
+                self.end_time
+            }
+            fn total_amount(&self) -> u64 {
+                self.total_amount
+            }
+            fn claimed_amount(&self) -> u64 {
+                self.claimed_amount
+            }
+            fn cliff_percentage(&self) -> u8 {
+                self.cliff_percentage
+            }
+            fn payment_interval(&self) -> i64 {
+                self.payment_interval
+            }
+            fn revoked_at(&self) -> i64 {
+                self.revoked_at
+            }
+            fn paused_at(&self) -> i64 {
+                self.paused_at
+            }
+            fn paused_duration(&self) -> i64 {
+                self.paused_duration
+            }
+            fn accelerated_amount(&self) -> u64 {
+                self.accelerated_amount
+            }
+        }
+    };
+}
+
+impl_schedule!(Vesting);
+impl_schedule!(VestingZeroCopy);
+
+// Lets `Account<Vesting>` and `RefMut<VestingZeroCopy>` be passed as they are
+impl<T> Schedule for T
+where
+    T: std::ops::Deref,
+    T::Target: Schedule,
+{
+    fn start_time(&self) -> i64 {
+        (**self).start_time()
+    }
+    fn end_time(&self) -> i64 {
+        (**self).end_time()
+    }
+    fn total_amount(&self) -> u64 {
+        (**self).total_amount()
+    }
+    fn claimed_amount(&self) -> u64 {
+        (**self).claimed_amount()
+    }
+    fn cliff_percentage(&self) -> u8 {
+        (**self).cliff_percentage()
+    }
+    fn payment_interval(&self) -> i64 {
+        (**self).payment_interval()
+    }
+    fn revoked_at(&self) -> i64 {
+        (**self).revoked_at()
+    }
+    fn paused_at(&self) -> i64 {
+        (**self).paused_at()
+    }
+    fn paused_duration(&self) -> i64 {
+        (**self).paused_duration()
+    }
+    fn accelerated_amount(&self) -> u64 {
+        (**self).accelerated_amount()
+    }
+}
+
+/// Maps a wall-clock timestamp onto the schedule's own timeline, with paused time removed.
+/// While paused the schedule time stands still at the moment the pause started.
+pub fn schedule_time(vesting: &impl Schedule, now: i64) -> i64 {
+    let now = if vesting.paused_at() > 0 {
+        now.min(vesting.paused_at())
+    } else {
+        now
+    };
+    now.saturating_sub(vesting.paused_duration())
+}
+
+/// How much of the pause in progress, if any, up to `now` was lost to vesting. Time paused
+/// before the (already shifted) start doesn't count, since nothing was vesting yet.
+pub fn paused_window(vesting: &impl Schedule, now: i64) -> i64 {
+    if vesting.paused_at() == 0 {
+        return 0;
+    }
+    let from = vesting.paused_at().max(
+        vesting
+            .start_time()
+            .saturating_add(vesting.paused_duration()),
+    );
+    now.saturating_sub(from).max(0)
+}
+
+/// The end time shifted by every pause so far, including one still in progress at `now`.
+pub fn effective_end_time(vesting: &impl Schedule, now: i64) -> i64 {
+    let ongoing = paused_window(vesting, now);
+    vesting
+        .end_time()
+        .saturating_add(vesting.paused_duration())
+        .saturating_add(ongoing)
+}
+
+/// Validates schedule parameters the way `initialize` does.
+pub fn validate_schedule(
+    start_time: i64,
+    end_time: i64,
+    cliff_percentage: u8,
+    payment_interval: Option<i64>,
+) -> Result<()> {
+    require!(end_time > start_time, VestingError::InvalidTimeRange);
+    require!(cliff_percentage <= 100, VestingError::InvalidCliff);
+    if let Some(interval) = payment_interval {
+        require!(interval > 0, VestingError::InvalidInterval);
+    }
+    Ok(())
+}
 
-pub fn calculate_claimable_amount(vesting: &Vesting, now: i64) -> Result<u64> {
-    let now = now.min(vesting.end_time);
+/// Checks the name fits in `Vesting::name` and has no null bytes, the way `initialize` does.
+pub fn validate_name(name: &str) -> Result<()> {
+    require!(
+        name.len() <= 32 && !name.contains('\0'),
+        VestingError::InvalidName
+    );
+    Ok(())
+}
+
+/// Total amount vested at `now` according to the schedule, ignoring claims and revocation.
+/// Any accelerated amount counts as vested from the moment it was granted.
+pub fn calculate_vested_amount(vesting: &impl Schedule, now: i64) -> Result<u64> {
+    let vested = calculate_curve_amount(vesting, now)?
+        .saturating_add(vesting.accelerated_amount())
+        .min(vesting.total_amount());
+    Ok(vested)
+}
+
+fn calculate_curve_amount(vesting: &impl Schedule, now: i64) -> Result<u64> {
+    let now = schedule_time(vesting, now).min(vesting.end_time());
 
-    if vesting.revoked_at > 0 || now < vesting.start_time {
+    if now < vesting.start_time() {
         return Ok(0);
     }
 
-    if now == vesting.end_time {
-        return Ok(vesting.total_amount - vesting.claimed_amount);
+    if now == vesting.end_time() {
+        return Ok(vesting.total_amount());
     }
 
-    let cliff_amount = vesting.total_amount * vesting.cliff_percentage as u64 / 100;
+    // Widened so no amount or timestamp, even of a corrupt account, can overflow
+    let total_amount = vesting.total_amount() as u128;
+    let cliff_amount = total_amount * vesting.cliff_percentage() as u128 / 100;
 
-    let linear_amount = vesting
-        .total_amount
+    let linear_amount = total_amount
         .checked_sub(cliff_amount)
         .ok_or(VestingError::MathOverflow)?;
 
-    let elapsed = now - vesting.start_time;
-    let duration = vesting.end_time - vesting.start_time;
+    // start_time <= now < end_time, so both are positive
+    let elapsed = (now as i128 - vesting.start_time() as i128) as u128;
+    let duration = (vesting.end_time() as i128 - vesting.start_time() as i128) as u128;
 
-    let vested = if vesting.payment_interval > 0 {
-        let total_intervals = duration / vesting.payment_interval;
-        let interval_count = elapsed / vesting.payment_interval;
+    let vested = if vesting.payment_interval() > 0 {
+        let payment_interval = vesting.payment_interval() as u128;
+        let total_intervals = duration / payment_interval;
+        let interval_count = elapsed / payment_interval;
 
-        if total_intervals == 0 {
-            if elapsed >= duration {
-                linear_amount
-            } else {
-                0
-            }
-        } else {
-            let amount_per_interval = linear_amount
-                .checked_div(total_intervals as u64)
-                .ok_or(VestingError::MathOverflow)?;
-            amount_per_interval
-                .checked_mul(interval_count as u64)
-                .ok_or(VestingError::MathOverflow)?
+        match linear_amount.checked_div(total_intervals) {
+            Some(amount_per_interval) => amount_per_interval * interval_count,
+            None if elapsed >= duration => linear_amount,
+            None => 0,
         }
     } else {
-        linear_amount
-            .checked_mul(elapsed as u64)
-            .ok_or(VestingError::MathOverflow)?
-            .checked_div(duration as u64)
-            .ok_or(VestingError::MathOverflow)?
+        linear_amount * elapsed / duration
     };
 
-    let mut total_vested = cliff_amount + vested;
-    if total_vested > vesting.total_amount {
-        total_vested = vesting.total_amount;
+    let total_vested = (cliff_amount + vested).min(total_amount);
+
+    Ok(total_vested as u64)
+}
+
+pub fn calculate_claimable_amount(vesting: &impl Schedule, now: i64) -> Result<u64> {
+    if vesting.revoked_at() > 0 {
+        return Ok(0);
     }
 
-    if vesting.claimed_amount >= total_vested {
+    let total_vested = calculate_vested_amount(vesting, now)?;
+
+    if vesting.claimed_amount() >= total_vested {
         Ok(0)
     } else {
         total_vested
-            .checked_sub(vesting.claimed_amount)
+            .checked_sub(vesting.claimed_amount())
             .ok_or(VestingError::MathOverflow.into())
     }
 }
 
+/// Finds the first timestamp after `at` where the vested amount goes up, with the amount it
+/// goes up by. The vested amount never decreases, so a binary search up to the end is exact.
+pub fn next_unlock(vesting: &Vesting, at: i64) -> Result<Option<(i64, u64)>> {
+    if vesting.revoked_at > 0 || vesting.paused_at > 0 {
+        return Ok(None);
+    }
+
+    let vested = calculate_vested_amount(vesting, at)?;
+    if vested >= vesting.total_amount {
+        return Ok(None);
+    }
+
+    let mut low = at;
+    let mut high = effective_end_time(vesting, at).max(at.saturating_add(1));
+    while high.abs_diff(low) > 1 {
+        let mid = ((low as i128 + high as i128) / 2) as i64;
+        if calculate_vested_amount(vesting, mid)? > vested {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+
+    let unlocked = calculate_vested_amount(vesting, high)?.saturating_sub(vested);
+    Ok(Some((high, unlocked)))
+}
+
+/// Full breakdown of a schedule at `at`. Revoked schedules have nothing left to vest.
+pub fn calculate_status(vesting: &Vesting, at: i64) -> Result<VestingStatus> {
+    let effective_end_time = effective_end_time(vesting, at);
+    if vesting.revoked_at > 0 {
+        return Ok(VestingStatus {
+            at,
+            vested: vesting.claimed_amount,
+            claimed: vesting.claimed_amount,
+            effective_end_time,
+            ..Default::default()
+        });
+    }
+
+    let vested = calculate_vested_amount(vesting, at)?;
+    let (next_unlock_time, next_unlock_amount) = match next_unlock(vesting, at)? {
+        Some((time, amount)) => (Some(time), amount),
+        None => (None, 0),
+    };
+
+    Ok(VestingStatus {
+        at,
+        vested,
+        claimed: vesting.claimed_amount,
+        claimable: calculate_claimable_amount(vesting, at)?,
+        unvested: vesting.total_amount.saturating_sub(vested),
+        next_unlock_time,
+        next_unlock_amount,
+        effective_end_time,
+    })
+}
+
+/// Compares what the vault holds with what the schedule still owes. A revoked
+/// schedule owes nothing more.
+pub fn reconcile(vesting: &Vesting, vault_balance: u64) -> Reconciliation {
+    let owed = if vesting.revoked_at > 0 {
+        0
+    } else {
+        vesting.total_amount.saturating_sub(vesting.claimed_amount)
+    };
+    Reconciliation {
+        vault_balance,
+        owed,
+        surplus: vault_balance.saturating_sub(owed),
+        shortfall: owed.saturating_sub(vault_balance),
+    }
+}
+
+/// Enumerates the unlock events in `(from, to]`, at most `limit` of them, by walking
+/// `next_unlock`. Interval schedules yield one event per boundary plus any dust at the end
+/// and ignore `step`. Linear schedules vest a little nearly every second, so they need a
+/// positive `step` and yield one event per `(from + k * step, from + (k + 1) * step]` bucket
+/// that vests anything, stamped with the bucket end (capped at `to`).
+/// Nothing is projected for a paused schedule until it is resumed.
+pub fn unlock_calendar(
+    vesting: &Vesting,
+    from: i64,
+    to: i64,
+    step: Option<i64>,
+    limit: usize,
+) -> Result<Vec<UnlockEvent>> {
+    let step = if vesting.payment_interval > 0 {
+        None
+    } else {
+        match step {
+            Some(step) if step > 0 => Some(step),
+            _ => return err!(VestingError::InvalidInterval),
+        }
+    };
+
+    let mut events = Vec::new();
+    let mut cursor = from;
+    while events.len() < limit {
+        let Some((time, amount)) = next_unlock(vesting, cursor)? else {
+            break;
+        };
+        if time > to {
+            break;
+        }
+        let Some(step) = step else {
+            events.push(UnlockEvent {
+                time,
+                amount,
+                vested: calculate_vested_amount(vesting, time)?,
+            });
+            cursor = time;
+            continue;
+        };
+
+        // `time > cursor >= from`, so the bucket holding `time` starts at or after `from`
+        let index = (time as i128 - from as i128 - 1) / step as i128;
+        let start = (from as i128 + index * step as i128) as i64;
+        let end = (start as i128 + step as i128).min(to as i128) as i64;
+        let before = calculate_vested_amount(vesting, start)?;
+        let vested = calculate_vested_amount(vesting, end)?;
+        events.push(UnlockEvent {
+            time: end,
+            amount: vested.saturating_sub(before),
+            vested,
+        });
+        cursor = end;
+    }
+    Ok(events)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     fn vesting_template(overrides: Option<(u64, u64, u8, i64, i64, i64)>) -> Vesting {
         let (total_amount, claimed_amount, cliff_percentage, start_time, end_time, interval) =
@@ -85,6 +375,13 @@ mod tests {
             revoked_at: 0,
             last_claimed_at: 0,
             bump: 255,
+            paused_at: 0,
+            paused_duration: 0,
+            accelerated_amount: 0,
+            version: crate::VESTING_VERSION,
+            id: Some(1),
+            rescue_policy: Default::default(),
+            reserved: [0; crate::VESTING_RESERVED_LEN],
         }
     }
 
@@ -264,4 +561,458 @@ mod tests {
         // Only 1 interval fits, but duration = 100, so total_intervals = 0
         assert_eq!(result, 1000);
     }
+
+    #[test]
+    fn test_paused_stops_vesting() {
+        let mut vesting = vesting_template(Some((1000, 0, 0, 1000, 2000, 0)));
+        vesting.paused_at = 1200;
+        // Only the 200s before the pause count, no matter how long it lasts
+        assert_eq!(calculate_claimable_amount(&vesting, 1500).unwrap(), 200);
+        assert_eq!(calculate_claimable_amount(&vesting, 5000).unwrap(), 200);
+    }
+
+    #[test]
+    fn test_paused_pays_out_vested_before_pause() {
+        let mut vesting = vesting_template(None);
+        vesting.paused_at = 1500;
+        vesting.claimed_amount = 100;
+        // 200 cliff + 400 linear vested before the pause, 100 already claimed
+        assert_eq!(calculate_claimable_amount(&vesting, 1800).unwrap(), 500);
+    }
+
+    #[test]
+    fn test_resumed_excludes_paused_time() {
+        let mut vesting = vesting_template(Some((1000, 0, 0, 1000, 2000, 0)));
+        // Paused from 1200 to 1500
+        vesting.paused_duration = 300;
+        // 500s wall clock elapsed, 200s of it vesting
+        assert_eq!(calculate_claimable_amount(&vesting, 1500).unwrap(), 200);
+        assert_eq!(calculate_claimable_amount(&vesting, 1800).unwrap(), 500);
+    }
+
+    #[test]
+    fn test_resumed_shifts_end_time() {
+        let mut vesting = vesting_template(Some((1000, 0, 0, 1000, 2000, 100)));
+        vesting.paused_duration = 300;
+        assert_eq!(effective_end_time(&vesting, 2000), 2300);
+        assert_eq!(calculate_claimable_amount(&vesting, 2000).unwrap(), 700);
+        assert_eq!(calculate_claimable_amount(&vesting, 2299).unwrap(), 900);
+        assert_eq!(calculate_claimable_amount(&vesting, 2300).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_ongoing_pause_extends_end_time() {
+        let mut vesting = vesting_template(None);
+        vesting.paused_duration = 100;
+        vesting.paused_at = 1500;
+        assert_eq!(effective_end_time(&vesting, 1500), 2100);
+        assert_eq!(effective_end_time(&vesting, 1750), 2350);
+        assert_eq!(schedule_time(&vesting, 1750), 1400);
+    }
+
+    #[test]
+    fn test_pause_before_start_delays_cliff() {
+        let mut vesting = vesting_template(None);
+        vesting.paused_duration = 200;
+        // The start effectively moves from 1000 to 1200
+        assert_eq!(calculate_claimable_amount(&vesting, 1100).unwrap(), 0);
+        assert_eq!(calculate_claimable_amount(&vesting, 1200).unwrap(), 200);
+    }
+
+    #[test]
+    fn test_pause_entirely_before_start_shifts_nothing() {
+        let mut vesting = vesting_template(None);
+        vesting.paused_at = 500;
+        assert_eq!(paused_window(&vesting, 900), 0);
+        assert_eq!(effective_end_time(&vesting, 900), 2000);
+        // Resumed at 900: nothing is added to the paused duration
+        vesting.paused_duration += paused_window(&vesting, 900);
+        vesting.paused_at = 0;
+        assert_eq!(calculate_claimable_amount(&vesting, 1000).unwrap(), 200);
+        assert_eq!(calculate_claimable_amount(&vesting, 2000).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_pause_across_start_counts_from_start() {
+        let mut vesting = vesting_template(None);
+        vesting.paused_at = 500;
+        // Only the 200s after the start at 1000 were lost
+        assert_eq!(paused_window(&vesting, 1200), 200);
+        assert_eq!(effective_end_time(&vesting, 1200), 2200);
+        assert_eq!(calculate_claimable_amount(&vesting, 1200).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_vested_ignores_claims() {
+        let mut vesting = vesting_template(None);
+        vesting.claimed_amount = 500;
+        assert_eq!(calculate_vested_amount(&vesting, 1500).unwrap(), 600);
+        assert_eq!(calculate_vested_amount(&vesting, 2500).unwrap(), 1000);
+        assert_eq!(calculate_vested_amount(&vesting, 900).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_validate_schedule() {
+        assert!(validate_schedule(1000, 2000, 100, Some(1)).is_ok());
+        assert!(validate_schedule(1000, 2000, 0, None).is_ok());
+        assert_eq!(
+            validate_schedule(2000, 2000, 0, None).unwrap_err(),
+            VestingError::InvalidTimeRange.into()
+        );
+        assert_eq!(
+            validate_schedule(1000, 2000, 101, None).unwrap_err(),
+            VestingError::InvalidCliff.into()
+        );
+        assert_eq!(
+            validate_schedule(1000, 2000, 0, Some(0)).unwrap_err(),
+            VestingError::InvalidInterval.into()
+        );
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name(&"a".repeat(32)).is_ok());
+        assert_eq!(
+            validate_name(&"a".repeat(33)).unwrap_err(),
+            VestingError::InvalidName.into()
+        );
+        assert_eq!(
+            validate_name("a\0b").unwrap_err(),
+            VestingError::InvalidName.into()
+        );
+    }
+
+    #[test]
+    fn test_accelerated_amount_vests_immediately() {
+        let mut vesting = vesting_template(None);
+        vesting.accelerated_amount = 300;
+        // Before the start only the accelerated amount is vested
+        assert_eq!(calculate_claimable_amount(&vesting, 900).unwrap(), 300);
+        // 600 on the curve + 300 accelerated
+        assert_eq!(calculate_claimable_amount(&vesting, 1500).unwrap(), 900);
+        // Capped at the total
+        assert_eq!(calculate_claimable_amount(&vesting, 1800).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_accelerated_amount_respects_claims() {
+        let mut vesting = vesting_template(None);
+        vesting.claimed_amount = 600;
+        vesting.accelerated_amount = 1000;
+        assert_eq!(calculate_claimable_amount(&vesting, 1500).unwrap(), 400);
+    }
+
+    #[test]
+    fn test_status_breakdown() {
+        let vesting = vesting_template(Some((1000, 300, 10, 1000, 2000, 100)));
+        let status = calculate_status(&vesting, 1650).unwrap();
+        assert_eq!(status.vested, 640);
+        assert_eq!(status.claimed, 300);
+        assert_eq!(status.claimable, 340);
+        assert_eq!(status.unvested, 360);
+        assert_eq!(status.next_unlock_time, Some(1700));
+        assert_eq!(status.next_unlock_amount, 90);
+        assert_eq!(status.effective_end_time, 2000);
+    }
+
+    #[test]
+    fn test_reconcile_surplus_and_shortfall() {
+        let mut vesting = vesting_template(Some((1000, 300, 10, 1000, 2000, 0)));
+        let balanced = reconcile(&vesting, 700);
+        assert_eq!(
+            (balanced.owed, balanced.surplus, balanced.shortfall),
+            (700, 0, 0)
+        );
+
+        let donated = reconcile(&vesting, 750);
+        assert_eq!((donated.surplus, donated.shortfall), (50, 0));
+
+        let short = reconcile(&vesting, 690);
+        assert_eq!((short.surplus, short.shortfall), (0, 10));
+
+        vesting.revoked_at = 1500;
+        assert_eq!(reconcile(&vesting, 5).surplus, 5);
+    }
+
+    #[test]
+    fn test_next_unlock_before_start_is_cliff() {
+        let vesting = vesting_template(Some((1000, 0, 10, 1000, 2000, 100)));
+        assert_eq!(next_unlock(&vesting, 500).unwrap(), Some((1000, 100)));
+    }
+
+    #[test]
+    fn test_next_unlock_zero_cliff_skips_start() {
+        let vesting = vesting_template(Some((1000, 0, 0, 1000, 2000, 100)));
+        assert_eq!(next_unlock(&vesting, 500).unwrap(), Some((1100, 100)));
+    }
+
+    #[test]
+    fn test_next_unlock_linear() {
+        let vesting = vesting_template(Some((100, 0, 0, 1000, 2000, 0)));
+        // 1 token every 10 seconds
+        assert_eq!(next_unlock(&vesting, 1001).unwrap(), Some((1010, 1)));
+    }
+
+    #[test]
+    fn test_next_unlock_dust_at_end() {
+        // 1000 over 3 intervals leaves 1 token of dust for the end
+        let vesting = vesting_template(Some((1000, 0, 0, 1000, 1350, 100)));
+        assert_eq!(next_unlock(&vesting, 1250).unwrap(), Some((1300, 333)));
+        assert_eq!(next_unlock(&vesting, 1320).unwrap(), Some((1350, 1)));
+    }
+
+    #[test]
+    fn test_next_unlock_none_when_done_or_paused() {
+        let mut vesting = vesting_template(None);
+        assert_eq!(next_unlock(&vesting, 2000).unwrap(), None);
+        vesting.paused_at = 1500;
+        assert_eq!(next_unlock(&vesting, 1600).unwrap(), None);
+    }
+
+    #[test]
+    fn test_status_after_pause_uses_effective_end() {
+        let mut vesting = vesting_template(Some((1000, 0, 0, 1000, 2000, 0)));
+        vesting.paused_duration = 500;
+        let status = calculate_status(&vesting, 2000).unwrap();
+        assert_eq!(status.vested, 500);
+        assert_eq!(status.effective_end_time, 2500);
+    }
+
+    #[test]
+    fn test_status_revoked() {
+        let mut vesting = vesting_template(None);
+        vesting.claimed_amount = 200;
+        vesting.revoked_at = 1500;
+        let status = calculate_status(&vesting, 1600).unwrap();
+        assert_eq!(status.vested, 200);
+        assert_eq!(status.claimable, 0);
+        assert_eq!(status.unvested, 0);
+        assert_eq!(status.next_unlock_time, None);
+    }
+
+    #[test]
+    fn test_calendar_interval_boundaries() {
+        let vesting = vesting_template(Some((1000, 0, 10, 1000, 2000, 100)));
+        let events = unlock_calendar(&vesting, 0, 3000, None, 100).unwrap();
+        // Cliff at the start plus 10 intervals
+        assert_eq!(events.len(), 11);
+        assert_eq!(
+            events[0],
+            UnlockEvent {
+                time: 1000,
+                amount: 100,
+                vested: 100
+            }
+        );
+        assert_eq!(
+            events[6],
+            UnlockEvent {
+                time: 1600,
+                amount: 90,
+                vested: 640
+            }
+        );
+        assert_eq!(events.last().unwrap().time, 2000);
+        assert_eq!(events.last().unwrap().vested, 1000);
+    }
+
+    #[test]
+    fn test_calendar_matches_claimable_math() {
+        let vesting = vesting_template(Some((1000, 0, 7, 1000, 1950, 70)));
+        let events = unlock_calendar(&vesting, 0, 3000, None, 100).unwrap();
+        let mut previous = 0;
+        for event in &events {
+            assert_eq!(
+                calculate_claimable_amount(&vesting, event.time).unwrap(),
+                event.vested
+            );
+            assert_eq!(
+                calculate_claimable_amount(&vesting, event.time - 1).unwrap(),
+                previous
+            );
+            assert_eq!(event.vested - previous, event.amount);
+            previous = event.vested;
+        }
+        assert_eq!(previous, 1000);
+    }
+
+    #[test]
+    fn test_calendar_range_and_limit() {
+        let vesting = vesting_template(Some((1000, 0, 10, 1000, 2000, 100)));
+        let events = unlock_calendar(&vesting, 1000, 1300, None, 100).unwrap();
+        let times: Vec<i64> = events.iter().map(|e| e.time).collect();
+        assert_eq!(times, vec![1100, 1200, 1300]);
+
+        let events = unlock_calendar(&vesting, 0, 3000, None, 2).unwrap();
+        let times: Vec<i64> = events.iter().map(|e| e.time).collect();
+        assert_eq!(times, vec![1000, 1100]);
+    }
+
+    #[test]
+    fn test_calendar_linear_buckets() {
+        // 20% cliff at 1000, then 800 more vesting linearly until 2000
+        let vesting = vesting_template(None);
+        assert_eq!(
+            unlock_calendar(&vesting, 0, 3000, None, 100),
+            Err(VestingError::InvalidInterval.into())
+        );
+        assert_eq!(
+            unlock_calendar(&vesting, 0, 3000, Some(0), 100),
+            Err(VestingError::InvalidInterval.into())
+        );
+
+        let events = unlock_calendar(&vesting, 900, 3000, Some(250), 100).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                UnlockEvent {
+                    time: 1150,
+                    amount: 320,
+                    vested: 320
+                },
+                UnlockEvent {
+                    time: 1400,
+                    amount: 200,
+                    vested: 520
+                },
+                UnlockEvent {
+                    time: 1650,
+                    amount: 200,
+                    vested: 720
+                },
+                UnlockEvent {
+                    time: 1900,
+                    amount: 200,
+                    vested: 920
+                },
+                UnlockEvent {
+                    time: 2150,
+                    amount: 80,
+                    vested: 1000
+                },
+            ]
+        );
+
+        // The last bucket is cut off at `to`, and paging resumes from its end
+        let events = unlock_calendar(&vesting, 900, 1500, Some(250), 2).unwrap();
+        assert_eq!(events.last().unwrap().time, 1400);
+        let events = unlock_calendar(&vesting, 1400, 1500, Some(250), 100).unwrap();
+        assert_eq!(
+            events,
+            vec![UnlockEvent {
+                time: 1500,
+                amount: 80,
+                vested: 600
+            }]
+        );
+    }
+
+    #[test]
+    fn test_calendar_paused_is_empty() {
+        let mut vesting = vesting_template(None);
+        vesting.paused_at = 1200;
+        assert!(unlock_calendar(&vesting, 1200, 3000, Some(100), 100)
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Valid schedules, as `initialize` accepts them, with nothing claimed
+    fn valid_schedule() -> impl Strategy<Value = Vesting> {
+        (
+            any::<u64>(),
+            0..=100u8,
+            -10_000_000_000i64..10_000_000_000,
+            1..1_000_000_000i64,
+            prop::option::of(1..2_000_000_000i64),
+        )
+            .prop_map(|(total, cliff, start, duration, interval)| {
+                vesting_template(Some((
+                    total,
+                    0,
+                    cliff,
+                    start,
+                    start + duration,
+                    interval.unwrap_or(0),
+                )))
+            })
+    }
+
+    /// Any bytes a corrupt or hand-crafted account could hold
+    fn any_vesting() -> impl Strategy<Value = Vesting> {
+        (
+            (any::<u64>(), any::<u64>(), any::<u64>(), any::<u8>()),
+            (any::<i64>(), any::<i64>(), any::<i64>()),
+            (any::<i64>(), any::<i64>(), any::<i64>()),
+        )
+            .prop_map(
+                |(
+                    (total, claimed, accelerated, cliff),
+                    (start, end, interval),
+                    (revoked_at, paused_at, paused_duration),
+                )| {
+                    let mut vesting =
+                        vesting_template(Some((total, claimed, cliff, start, end, interval)));
+                    vesting.accelerated_amount = accelerated;
+                    vesting.revoked_at = revoked_at;
+                    vesting.paused_at = paused_at;
+                    vesting.paused_duration = paused_duration;
+                    vesting
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_claimable_is_monotonic_in_time(
+            vesting in valid_schedule(),
+            a in any::<i64>(),
+            b in any::<i64>(),
+        ) {
+            let (early, late) = (a.min(b), a.max(b));
+            prop_assert!(
+                calculate_claimable_amount(&vesting, early).unwrap()
+                    <= calculate_claimable_amount(&vesting, late).unwrap()
+            );
+        }
+
+        #[test]
+        fn prop_claimable_is_bounded_by_total(vesting in valid_schedule(), now in any::<i64>()) {
+            prop_assert!(calculate_claimable_amount(&vesting, now).unwrap() <= vesting.total_amount);
+        }
+
+        #[test]
+        fn prop_everything_vests_at_the_end(vesting in valid_schedule(), after in 0..=i64::MAX) {
+            let now = vesting.end_time.saturating_add(after);
+            prop_assert_eq!(calculate_claimable_amount(&vesting, now).unwrap(), vesting.total_amount);
+        }
+
+        #[test]
+        fn prop_nothing_vests_before_the_start(vesting in valid_schedule(), before in 1..=i64::MAX) {
+            let now = vesting.start_time.saturating_sub(before);
+            prop_assert_eq!(calculate_claimable_amount(&vesting, now).unwrap(), 0);
+        }
+
+        #[test]
+        fn prop_never_panics(vesting in any_vesting(), now in any::<i64>()) {
+            let _ = calculate_claimable_amount(&vesting, now);
+            let _ = calculate_status(&vesting, now);
+        }
+    }
+
+    #[test]
+    fn test_large_amounts_over_long_durations() {
+        // total * elapsed overflows u64, the widened math still splits it exactly
+        let vesting = vesting_template(Some((u64::MAX, 0, 0, 0, 4 * 365 * 86_400, 0)));
+        assert_eq!(
+            calculate_claimable_amount(&vesting, 2 * 365 * 86_400).unwrap(),
+            u64::MAX / 2
+        );
+    }
+
+    #[test]
+    fn test_inverted_time_range_never_vests() {
+        let vesting = vesting_template(Some((1000, 0, 20, 2000, 1000, 0)));
+        assert_eq!(calculate_claimable_amount(&vesting, 1500).unwrap(), 0);
+        assert_eq!(calculate_claimable_amount(&vesting, i64::MAX).unwrap(), 0);
+    }
 }
@@ -0,0 +1,7 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum RewardsError {
+    #[msg("Math overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenInterface},
+};
+use solana_vesting_program::{
+    cpi::{self, accounts::Claim},
+    program::SolanaVestingProgram,
+    ClaimResult, Vesting,
+};
+
+use crate::{error::RewardsError, Pool, POOL_SEED};
+
+/// Claims a reward granted by the pool on behalf of its beneficiary, who signs
+/// the transaction, and books the claimed amount returned by the vesting program.
+#[derive(Accounts)]
+pub struct ClaimReward<'info> {
+    #[account(mut, seeds = [POOL_SEED, mint.key().as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, constraint = vesting.creator == pool.key())]
+    pub vesting: Account<'info, Vesting>,
+    /// CHECK: `["vault", vesting]` of the vesting program
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: The beneficiary's associated token account, created by the vesting program if needed
+    #[account(mut)]
+    pub beneficiary_token_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: The vesting program's `Config`
+    pub vesting_config: UncheckedAccount<'info>,
+    /// CHECK: `["__event_authority"]` of the vesting program
+    #[cfg(feature = "event-cpi")]
+    pub event_authority: UncheckedAccount<'info>,
+
+    pub vesting_program: Program<'info, SolanaVestingProgram>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_reward_handler(ctx: Context<ClaimReward>) -> Result<ClaimResult> {
+    let accounts = &ctx.accounts;
+    let cpi_accounts = Claim {
+        vesting: accounts.vesting.to_account_info(),
+        vault: accounts.vault.to_account_info(),
+        beneficiary_token_account: accounts.beneficiary_token_account.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        mint: accounts.mint.to_account_info(),
+        beneficiary: accounts.beneficiary.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        associated_token_program: accounts.associated_token_program.to_account_info(),
+        config: accounts.vesting_config.to_account_info(),
+        #[cfg(feature = "event-cpi")]
+        event_authority: accounts.event_authority.to_account_info(),
+        #[cfg(feature = "event-cpi")]
+        program: accounts.vesting_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(accounts.vesting_program.to_account_info(), cpi_accounts);
+    let result = cpi::claim(cpi_ctx)?.get();
+
+    let pool = &mut ctx.accounts.pool;
+    pool.total_claimed = pool
+        .total_claimed
+        .checked_add(result.amount)
+        .ok_or(RewardsError::MathOverflow)?;
+
+    Ok(result)
+}
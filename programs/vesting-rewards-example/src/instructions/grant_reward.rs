@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_vesting_program::{
+    cpi::{self, accounts::InitializeVesting},
+    program::SolanaVestingProgram,
+};
+
+use crate::{error::RewardsError, Pool};
+
+/// Grants `amount` of the pool's tokens as a vesting schedule. The pool PDA
+/// signs as the schedule's `authority` and becomes its creator, the admin pays
+/// the rent. Every account of the vesting program is checked by it.
+#[derive(Accounts)]
+pub struct GrantReward<'info> {
+    #[account(mut, has_one = admin, has_one = mint)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut,
+        associated_token::authority = pool,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Only used as a key
+    pub beneficiary: UncheckedAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: `["vesting", pool, beneficiary, mint, pool.grants]` of the vesting program
+    #[account(mut)]
+    pub vesting: UncheckedAccount<'info>,
+    /// CHECK: `["vault", vesting]` of the vesting program
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: The vesting program's `Config`
+    pub vesting_config: UncheckedAccount<'info>,
+    /// CHECK: `["allowed_mint", mint]` of the vesting program
+    pub allowed_mint: UncheckedAccount<'info>,
+    /// CHECK: `["fee_waiver", pool]` of the vesting program
+    pub fee_waiver: UncheckedAccount<'info>,
+    /// CHECK: The vesting program's treasury, only needed when it charges a lamport fee
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    /// CHECK: The vesting program's treasury token account, only needed when it charges a token fee
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: `["__event_authority"]` of the vesting program
+    #[cfg(feature = "event-cpi")]
+    pub event_authority: UncheckedAccount<'info>,
+
+    pub vesting_program: Program<'info, SolanaVestingProgram>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn grant_reward_handler(
+    ctx: Context<GrantReward>,
+    amount: u64,
+    start_time: i64,
+    end_time: i64,
+    cliff_percentage: u8,
+    payment_interval: Option<i64>,
+    revocable: bool,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let id = accounts.pool.grants;
+
+    let cpi_accounts = InitializeVesting {
+        vesting: accounts.vesting.to_account_info(),
+        vault: accounts.vault.to_account_info(),
+        payer: accounts.admin.to_account_info(),
+        authority: accounts.pool.to_account_info(),
+        source_token_account: accounts.pool_token_account.to_account_info(),
+        beneficiary: accounts.beneficiary.to_account_info(),
+        mint: accounts.mint.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        config: accounts.vesting_config.to_account_info(),
+        allowed_mint: accounts.allowed_mint.to_account_info(),
+        treasury: accounts.treasury.as_ref().map(|a| a.to_account_info()),
+        treasury_token_account: accounts
+            .treasury_token_account
+            .as_ref()
+            .map(|a| a.to_account_info()),
+        fee_waiver: accounts.fee_waiver.to_account_info(),
+        creator_index: None,
+        beneficiary_index: None,
+        #[cfg(feature = "event-cpi")]
+        event_authority: accounts.event_authority.to_account_info(),
+        #[cfg(feature = "event-cpi")]
+        program: accounts.vesting_program.to_account_info(),
+    };
+    let seeds = accounts.pool.signer_seeds();
+    let signer_seeds = &[&seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        accounts.vesting_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    cpi::initialize(
+        cpi_ctx,
+        id,
+        amount,
+        start_time,
+        end_time,
+        cliff_percentage,
+        payment_interval,
+        format!("Reward #{id}"),
+        revocable,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.grants = pool
+        .grants
+        .checked_add(1)
+        .ok_or(RewardsError::MathOverflow)?;
+    pool.total_granted = pool
+        .total_granted
+        .checked_add(amount)
+        .ok_or(RewardsError::MathOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{Pool, POOL_SEED};
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        seeds = [POOL_SEED, mint.key().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + Pool::INIT_SPACE,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Holds the rewards, funded by plain token transfers
+    #[account(
+        init,
+        payer = admin,
+        associated_token::authority = pool,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_pool_handler(ctx: Context<InitializePool>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.admin = ctx.accounts.admin.key();
+    pool.mint = ctx.accounts.mint.key();
+    pool.grants = 0;
+    pool.total_granted = 0;
+    pool.total_claimed = 0;
+    pool.total_revoked = 0;
+    pool.bump = ctx.bumps.pool;
+    Ok(())
+}
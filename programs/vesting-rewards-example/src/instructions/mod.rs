@@ -0,0 +1,9 @@
+pub mod claim_reward;
+pub mod grant_reward;
+pub mod initialize_pool;
+pub mod revoke_reward;
+
+pub use claim_reward::*;
+pub use grant_reward::*;
+pub use initialize_pool::*;
+pub use revoke_reward::*;
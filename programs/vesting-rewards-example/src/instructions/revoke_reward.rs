@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_vesting_program::{
+    cpi::{self, accounts::Revoke},
+    program::SolanaVestingProgram,
+    RevokeResult, Vesting,
+};
+
+use crate::{error::RewardsError, Pool};
+
+/// Revokes a reward granted by the pool, signed by the pool PDA as creator.
+/// The unvested tokens go back to the pool's token account.
+#[derive(Accounts)]
+pub struct RevokeReward<'info> {
+    #[account(mut, has_one = admin, has_one = mint)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut,
+        associated_token::authority = pool,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = vesting.creator == pool.key())]
+    pub vesting: Account<'info, Vesting>,
+    /// CHECK: `["vault", vesting]` of the vesting program
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: The vesting program's `Config`
+    pub vesting_config: UncheckedAccount<'info>,
+    /// CHECK: `["__event_authority"]` of the vesting program
+    #[cfg(feature = "event-cpi")]
+    pub event_authority: UncheckedAccount<'info>,
+
+    pub vesting_program: Program<'info, SolanaVestingProgram>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn revoke_reward_handler(ctx: Context<RevokeReward>) -> Result<RevokeResult> {
+    let accounts = &ctx.accounts;
+    let cpi_accounts = Revoke {
+        vesting: accounts.vesting.to_account_info(),
+        vault: accounts.vault.to_account_info(),
+        recipient_account: accounts.pool_token_account.to_account_info(),
+        creator: accounts.pool.to_account_info(),
+        mint: accounts.mint.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        config: accounts.vesting_config.to_account_info(),
+        #[cfg(feature = "event-cpi")]
+        event_authority: accounts.event_authority.to_account_info(),
+        #[cfg(feature = "event-cpi")]
+        program: accounts.vesting_program.to_account_info(),
+    };
+    let seeds = accounts.pool.signer_seeds();
+    let signer_seeds = &[&seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        accounts.vesting_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    let result = cpi::revoke(cpi_ctx)?.get();
+
+    let pool = &mut ctx.accounts.pool;
+    pool.total_revoked = pool
+        .total_revoked
+        .checked_add(result.amount)
+        .ok_or(RewardsError::MathOverflow)?;
+
+    Ok(result)
+}
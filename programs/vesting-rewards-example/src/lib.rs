@@ -0,0 +1,54 @@
+//! Example of a program creating vesting schedules through CPI: a rewards pool
+//! whose tokens are granted to beneficiaries as vesting schedules, with the
+//! pool PDA as their creator.
+
+#![allow(unexpected_cfgs)]
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use anchor_lang::prelude::*;
+use solana_vesting_program::{ClaimResult, RevokeResult};
+
+pub use instructions::*;
+pub use state::*;
+
+declare_id!("9qNmjbq5PhzD96Bj2Zhd7sAVzs6A98S8v7WoVgaVvBQa");
+
+#[program]
+pub mod vesting_rewards_example {
+    use super::*;
+
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        initialize_pool::initialize_pool_handler(ctx)
+    }
+
+    pub fn grant_reward(
+        ctx: Context<GrantReward>,
+        amount: u64,
+        start_time: i64,
+        end_time: i64,
+        cliff_percentage: u8,
+        payment_interval: Option<i64>,
+        revocable: bool,
+    ) -> Result<()> {
+        grant_reward::grant_reward_handler(
+            ctx,
+            amount,
+            start_time,
+            end_time,
+            cliff_percentage,
+            payment_interval,
+            revocable,
+        )
+    }
+
+    pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<ClaimResult> {
+        claim_reward::claim_reward_handler(ctx)
+    }
+
+    pub fn revoke_reward(ctx: Context<RevokeReward>) -> Result<RevokeResult> {
+        revoke_reward::revoke_reward_handler(ctx)
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+/// Seed prefix of `Pool` accounts, followed by the mint
+pub const POOL_SEED: &[u8] = b"pool";
+
+/// A pool of reward tokens, held in the pool's associated token account
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    /// Who may grant rewards
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    /// Number of grants so far, used as the ID of the next vesting schedule
+    pub grants: u64,
+    /// Sum of all granted amounts
+    pub total_granted: u64,
+    /// Sum of all amounts claimed through `claim_reward`
+    pub total_claimed: u64,
+    /// Sum of all amounts returned to the pool by `revoke_reward`
+    pub total_revoked: u64,
+    pub bump: u8,
+}
+
+impl Pool {
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [
+            POOL_SEED,
+            self.mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
@@ -0,0 +1,173 @@
+// tests/compute-units.test.ts
+// Compares compute units of the Borsh `Vesting` account against the zero-copy `VestingZeroCopy`.
+import * as anchor from "@coral-xyz/anchor";
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  getAssociatedTokenAddressSync,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { ensureConfig } from "./config";
+import IDLz from "../target/idl/solana_vesting_program.json";
+import { SolanaVestingProgram } from "../target/types/solana_vesting_program";
+
+const TOKENID = TOKEN_2022_PROGRAM_ID;
+let mint: PublicKey;
+let program: anchor.Program<SolanaVestingProgram>;
+let creator: NodeWallet;
+let connection: anchor.web3.Connection;
+let creatorTokenAccount: PublicKey;
+
+type Variant = "borsh" | "zeroCopy";
+const seedPrefix: Record<Variant, string> = { borsh: "vesting", zeroCopy: "vesting_zc" };
+
+async function unitsOf(signature: string): Promise<number> {
+  const latestBlockhash = await connection.getLatestBlockhash();
+  await connection.confirmTransaction({ signature, ...latestBlockhash }, "confirmed");
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  return tx!.meta!.computeUnitsConsumed!;
+}
+
+async function simulatedUnitsOf(tx: anchor.web3.Transaction, feePayer: PublicKey): Promise<number> {
+  tx.feePayer = feePayer;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+  const result = await connection.simulateTransaction(tx);
+  return result.value.unitsConsumed!;
+}
+
+describe("compute units", () => {
+  const totalAmount = new anchor.BN(1_000_000);
+  const results: Record<string, Record<Variant, number>> = {
+    claim: { borsh: 0, zeroCopy: 0 },
+    estimate: { borsh: 0, zeroCopy: 0 },
+    revoke: { borsh: 0, zeroCopy: 0 },
+  };
+
+  async function setupVesting(variant: Variant) {
+    const beneficiary = anchor.web3.Keypair.generate();
+    const name = `CU ${variant}`;
+    const id = new anchor.BN(0);
+    const [vesting] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(seedPrefix[variant]),
+        creator.publicKey.toBuffer(),
+        beneficiary.publicKey.toBuffer(),
+        mint.toBuffer(),
+        id.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const now = Math.floor(Date.now() / 1000);
+    const args = [
+      id,
+      totalAmount,
+      new anchor.BN(now - 10),
+      new anchor.BN(now + 1000),
+      20,
+      new anchor.BN(1),
+      name,
+      true,
+    ] as const;
+    const method =
+      variant === "borsh" ? program.methods.initialize(...args) : program.methods.initializeZeroCopy(...args);
+    await method
+      .accounts({
+        sourceTokenAccount: creatorTokenAccount,
+        beneficiary: beneficiary.publicKey,
+        mint,
+        tokenProgram: TOKENID,
+      })
+      .rpc();
+
+    const airdropSignature = await connection.requestAirdrop(beneficiary.publicKey, LAMPORTS_PER_SOL);
+    const latestBlockhash = await connection.getLatestBlockhash();
+    await connection.confirmTransaction({ signature: airdropSignature, ...latestBlockhash });
+    const provider = new anchor.AnchorProvider(connection, new anchor.Wallet(beneficiary));
+    const beneficiaryProgram = new anchor.Program(IDLz as SolanaVestingProgram, provider);
+
+    return { beneficiary, beneficiaryProgram, vesting };
+  }
+
+  beforeAll(async () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    connection = provider.connection;
+    creator = provider.wallet as NodeWallet;
+    program = anchor.workspace.SolanaVestingProgram as anchor.Program<SolanaVestingProgram>;
+    await ensureConfig(program, creator.publicKey);
+    mint = await createMint(connection, creator.payer, creator.publicKey, null, 6, undefined, undefined, TOKENID);
+    creatorTokenAccount = await createAssociatedTokenAccount(
+      connection,
+      creator.payer,
+      mint,
+      creator.publicKey,
+      {},
+      TOKENID
+    );
+    await mintTo(
+      connection,
+      creator.payer,
+      mint,
+      creatorTokenAccount,
+      creator.publicKey,
+      totalAmount.toNumber() * 10,
+      undefined,
+      undefined,
+      TOKENID
+    );
+  });
+
+  afterAll(() => {
+    console.table(
+      Object.entries(results).map(([instruction, units]) => ({
+        instruction,
+        borsh: units.borsh,
+        zeroCopy: units.zeroCopy,
+        delta: units.zeroCopy - units.borsh,
+      }))
+    );
+  });
+
+  for (const variant of ["borsh", "zeroCopy"] as Variant[]) {
+    it(`Measures claim, estimate and revoke for the ${variant} account`, async () => {
+      const { beneficiary, beneficiaryProgram, vesting } = await setupVesting(variant);
+      const beneficiaryTokenAccount = getAssociatedTokenAddressSync(
+        mint,
+        beneficiary.publicKey,
+        false,
+        TOKENID
+      );
+      // Create the beneficiary ATA up front so `claim` doesn't pay for `init_if_needed`
+      await createAssociatedTokenAccount(connection, creator.payer, mint, beneficiary.publicKey, {}, TOKENID);
+
+      const estimate =
+        variant === "borsh" ? beneficiaryProgram.methods.estimate() : beneficiaryProgram.methods.estimateZeroCopy();
+      results.estimate[variant] = await simulatedUnitsOf(
+        await estimate.accounts({ vesting } as any).transaction(),
+        beneficiary.publicKey
+      );
+
+      const claim =
+        variant === "borsh" ? beneficiaryProgram.methods.claim() : beneficiaryProgram.methods.claimZeroCopy();
+      results.claim[variant] = await unitsOf(
+        await claim.accounts({ vesting, mint, beneficiaryTokenAccount, tokenProgram: TOKENID } as any).rpc()
+      );
+
+      const revoke = variant === "borsh" ? program.methods.revoke() : program.methods.revokeZeroCopy();
+      results.revoke[variant] = await unitsOf(
+        await revoke.accounts({ vesting, recipientAccount: creatorTokenAccount, mint, tokenProgram: TOKENID } as any).rpc()
+      );
+
+      for (const units of Object.values(results)) {
+        expect(units[variant]).toBeGreaterThan(0);
+      }
+    });
+  }
+});
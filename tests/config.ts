@@ -0,0 +1,29 @@
+// tests/config.ts
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+
+import { SolanaVestingProgram } from "../target/types/solana_vesting_program";
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+export function configAddress(programId: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], programId)[0];
+}
+
+// Creates the program config once per validator, the provider wallet is the upgrade authority on localnet
+export async function ensureConfig(program: anchor.Program<SolanaVestingProgram>, admin: PublicKey) {
+  const config = configAddress(program.programId);
+  if (await program.provider.connection.getAccountInfo(config)) {
+    return config;
+  }
+
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+  await program.methods
+    .initializeConfig(admin)
+    .accounts({ programData } as any)
+    .rpc();
+  return config;
+}
@@ -0,0 +1,192 @@
+// tests/rewards-example.test.ts
+// Creates vesting schedules through CPI from the example rewards program, signed by its pool PDA.
+import * as anchor from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAssociatedTokenAddressSync,
+  getAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { configAddress, ensureConfig } from "./config";
+import { SolanaVestingProgram } from "../target/types/solana_vesting_program";
+import { VestingRewardsExample } from "../target/types/vesting_rewards_example";
+
+const TOKENID = TOKEN_2022_PROGRAM_ID;
+
+describe("rewards example", () => {
+  let vestingProgram: anchor.Program<SolanaVestingProgram>;
+  let rewards: anchor.Program<VestingRewardsExample>;
+  let admin: NodeWallet;
+  let connection: anchor.web3.Connection;
+  let mint: PublicKey;
+  let pool: PublicKey;
+
+  function vestingAccounts(beneficiary: PublicKey, id: number) {
+    const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, vestingProgram.programId)[0];
+    const vesting = pda([
+      Buffer.from("vesting"),
+      pool.toBuffer(),
+      beneficiary.toBuffer(),
+      mint.toBuffer(),
+      new anchor.BN(id).toArrayLike(Buffer, "le", 8),
+    ]);
+    return {
+      vesting,
+      vault: pda([Buffer.from("vault"), vesting.toBuffer()]),
+      vestingConfig: configAddress(vestingProgram.programId),
+      allowedMint: pda([Buffer.from("allowed_mint"), mint.toBuffer()]),
+      feeWaiver: pda([Buffer.from("fee_waiver"), pool.toBuffer()]),
+      eventAuthority: pda([Buffer.from("__event_authority")]),
+      treasury: null,
+      treasuryTokenAccount: null,
+    };
+  }
+
+  // The `[amount, claimed_amount, remaining]` a program returned in the transaction
+  async function returnedAmounts(signature: string, programId: PublicKey) {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const prefix = `Program return: ${programId.toBase58()} `;
+    const log = tx.meta.logMessages.filter((line) => line.startsWith(prefix)).pop();
+    const data = Buffer.from(log.slice(prefix.length), "base64");
+    return [0, 8, 16].map((offset) => Number(data.readBigUInt64LE(offset)));
+  }
+
+  beforeAll(async () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    connection = provider.connection;
+    admin = provider.wallet as NodeWallet;
+    vestingProgram = anchor.workspace.SolanaVestingProgram as anchor.Program<SolanaVestingProgram>;
+    rewards = anchor.workspace.VestingRewardsExample as anchor.Program<VestingRewardsExample>;
+    await ensureConfig(vestingProgram, admin.publicKey);
+
+    mint = await createMint(connection, admin.payer, admin.publicKey, null, 6, undefined, undefined, TOKENID);
+    [pool] = PublicKey.findProgramAddressSync([Buffer.from("pool"), mint.toBuffer()], rewards.programId);
+    await rewards.methods.initializePool().accounts({ mint, tokenProgram: TOKENID }).rpc();
+    const poolTokenAccount = getAssociatedTokenAddressSync(mint, pool, true, TOKENID);
+    await mintTo(connection, admin.payer, mint, poolTokenAccount, admin.publicKey, 1_000_000, [], undefined, TOKENID);
+  });
+
+  it("Grants rewards as vesting schedules created by the pool", async () => {
+    const beneficiary = anchor.web3.Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+
+    for (const id of [0, 1]) {
+      await rewards.methods
+        .grantReward(new anchor.BN(100_000), new anchor.BN(now), new anchor.BN(now + 100), 10, null, false)
+        .accounts({
+          pool,
+          beneficiary: beneficiary.publicKey,
+          mint,
+          tokenProgram: TOKENID,
+          ...vestingAccounts(beneficiary.publicKey, id),
+        } as any)
+        .rpc();
+    }
+
+    const { vesting, vault } = vestingAccounts(beneficiary.publicKey, 1);
+    const schedule = await vestingProgram.account.vesting.fetch(vesting);
+    expect(schedule.creator.toBase58()).toBe(pool.toBase58());
+    expect(schedule.beneficiary.toBase58()).toBe(beneficiary.publicKey.toBase58());
+    expect(schedule.totalAmount.toNumber()).toBe(100_000);
+    expect(schedule.name).toBe("Reward #1");
+
+    const vaultAccount = await getAccount(connection, vault, undefined, TOKENID);
+    expect(vaultAccount.amount.toString()).toBe("100000");
+
+    const poolAccount = await rewards.account.pool.fetch(pool);
+    expect(poolAccount.grants.toNumber()).toBe(2);
+    expect(poolAccount.totalGranted.toNumber()).toBe(200_000);
+    const poolTokenAccount = getAssociatedTokenAddressSync(mint, pool, true, TOKENID);
+    const poolTokens = await getAccount(connection, poolTokenAccount, undefined, TOKENID);
+    expect(poolTokens.amount.toString()).toBe("800000");
+  });
+
+  it("Books the amounts returned by claim and revoke through CPI", async () => {
+    const beneficiary = anchor.web3.Keypair.generate();
+    const signature = await connection.requestAirdrop(beneficiary.publicKey, LAMPORTS_PER_SOL);
+    await connection.confirmTransaction({ signature, ...(await connection.getLatestBlockhash()) });
+    const now = Math.floor(Date.now() / 1000);
+    const before = await rewards.account.pool.fetch(pool);
+    const id = before.grants.toNumber();
+    const accounts = vestingAccounts(beneficiary.publicKey, id);
+
+    await rewards.methods
+      .grantReward(new anchor.BN(100_000), new anchor.BN(now - 50), new anchor.BN(now + 50), 0, null, true)
+      .accounts({ pool, beneficiary: beneficiary.publicKey, mint, tokenProgram: TOKENID, ...accounts } as any)
+      .rpc();
+
+    const beneficiaryRewards = new anchor.Program(
+      rewards.idl,
+      new anchor.AnchorProvider(connection, new anchor.Wallet(beneficiary), {})
+    ) as anchor.Program<VestingRewardsExample>;
+    const beneficiaryTokenAccount = getAssociatedTokenAddressSync(mint, beneficiary.publicKey, false, TOKENID);
+    const claimSignature = await beneficiaryRewards.methods
+      .claimReward()
+      .accounts({
+        vesting: accounts.vesting,
+        vault: accounts.vault,
+        beneficiaryTokenAccount,
+        mint,
+        vestingConfig: accounts.vestingConfig,
+        eventAuthority: accounts.eventAuthority,
+        tokenProgram: TOKENID,
+      } as any)
+      .rpc({ commitment: "confirmed" });
+
+    const received = Number((await getAccount(connection, beneficiaryTokenAccount, undefined, TOKENID)).amount);
+    expect(received).toBeGreaterThan(0);
+    // The vesting program's return data, forwarded by the example program
+    const [claimed, claimedTotal, remaining] = await returnedAmounts(claimSignature, vestingProgram.programId);
+    expect([claimed, claimedTotal, remaining]).toEqual([received, received, 100_000 - received]);
+    expect(await returnedAmounts(claimSignature, rewards.programId)).toEqual([claimed, claimedTotal, remaining]);
+
+    const revokeSignature = await rewards.methods
+      .revokeReward()
+      .accounts({
+        pool,
+        mint,
+        vesting: accounts.vesting,
+        vault: accounts.vault,
+        vestingConfig: accounts.vestingConfig,
+        eventAuthority: accounts.eventAuthority,
+        tokenProgram: TOKENID,
+      } as any)
+      .rpc({ commitment: "confirmed" });
+    expect(await returnedAmounts(revokeSignature, rewards.programId)).toEqual([100_000 - received, received, 0]);
+
+    const after = await rewards.account.pool.fetch(pool);
+    expect(after.totalClaimed.sub(before.totalClaimed).toNumber()).toBe(received);
+    expect(after.totalRevoked.sub(before.totalRevoked).toNumber()).toBe(100_000 - received);
+  });
+
+  it("Fails to grant when the pool's token account runs short", async () => {
+    const beneficiary = anchor.web3.Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+    const { grants } = await rewards.account.pool.fetch(pool);
+
+    let threw = false;
+    try {
+      await rewards.methods
+        .grantReward(new anchor.BN(10_000_000), new anchor.BN(now), new anchor.BN(now + 100), 0, null, false)
+        .accounts({
+          pool,
+          beneficiary: beneficiary.publicKey,
+          mint,
+          tokenProgram: TOKENID,
+          ...vestingAccounts(beneficiary.publicKey, grants.toNumber()),
+        } as any)
+        .rpc();
+    } catch (e) {
+      threw = true;
+    }
+    expect(threw).toBe(true);
+  });
+});
@@ -11,17 +11,23 @@ import { LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import * as assert from "assert";
 
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { configAddress, ensureConfig } from "./config";
 import IDLz from "../target/idl/solana_vesting_program.json";
 import { SolanaVestingProgram } from "../target/types/solana_vesting_program";
 
 const PROGRAM_ID = new PublicKey(IDLz.address);
 const TOKENID = TOKEN_2022_PROGRAM_ID;
+// Whether the program was built with the `event-cpi` feature, which adds `eventAuthority` to `claim`
+const EVENT_CPI = IDLz.instructions
+  .find((ix) => ix.name === "claim")
+  .accounts.some((account) => account.name === "event_authority");
 let creatorTokenAccountCreated = false;
 let mint: anchor.web3.PublicKey;
 let program: anchor.Program<SolanaVestingProgram>;
 let creator: NodeWallet;
 let connection: anchor.web3.Connection;
 let creatorTokenAccount: anchor.web3.PublicKey;
+let nextId = 0;
 
 describe("vesting", () => {
   const totalAmount = new anchor.BN(1_000_000);
@@ -31,6 +37,7 @@ describe("vesting", () => {
     let beneficiary: anchor.web3.Keypair;
     let beneficiaryTokenAccount: anchor.web3.PublicKey;
     let vault, vesting: anchor.web3.PublicKey;
+    let initSignature: string;
     const name = `Vesting Cliff ${cliffPercentage}%`;
     // let vault: anchor.web3.PublicKey;
     try {
@@ -68,8 +75,15 @@ describe("vesting", () => {
         );
       }
       let _Bump: number;
+      const id = new anchor.BN(nextId++);
       [vesting, _Bump] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("vesting"), beneficiary.publicKey.toBuffer(), mint.toBuffer(), Buffer.from(name)],
+        [
+          Buffer.from("vesting"),
+          creator.publicKey.toBuffer(),
+          beneficiary.publicKey.toBuffer(),
+          mint.toBuffer(),
+          id.toArrayLike(Buffer, "le", 8),
+        ],
         program.programId
       );
       [vault, _Bump] = anchor.web3.PublicKey.findProgramAddressSync(
@@ -79,8 +93,9 @@ describe("vesting", () => {
 
       const now = Math.floor(Date.now() / 1000);
 
-      await program.methods
+      initSignature = await program.methods
         .initialize(
+          id,
           totalAmount,
           new anchor.BN(now + cliffDelay),
           new anchor.BN(now + cliffDelay + 10),
@@ -97,13 +112,31 @@ describe("vesting", () => {
           tokenProgram: TOKENID,
         })
         .signers([])
-        .rpc();
+        .rpc({ commitment: "confirmed" });
     } catch (e) {
       console.error("Error initializing vesting:", e);
       throw e;
     }
 
-    return { beneficiary, beneficiaryTokenAccount, vesting, vault };
+    return { beneficiary, beneficiaryTokenAccount, vesting, vault, initSignature };
+  }
+
+  // Events emitted through self-CPI, decoded from the inner instructions invoking the program
+  async function cpiEvents(signature: string) {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const accountKeys = tx.transaction.message.staticAccountKeys;
+    return tx.meta.innerInstructions
+      .flatMap((inner) => inner.instructions)
+      .filter((ix) => accountKeys[ix.programIdIndex].equals(program.programId))
+      .map((ix) => {
+        // Skip the 8 byte event instruction tag
+        const data = anchor.utils.bytes.bs58.decode(ix.data).subarray(8);
+        return program.coder.events.decode(anchor.utils.bytes.base64.encode(Buffer.from(data)));
+      })
+      .filter((event) => event !== null);
   }
 
   async function airdropTokens(to: PublicKey) {
@@ -133,6 +166,7 @@ describe("vesting", () => {
     connection = provider.connection;
     creator = provider.wallet as NodeWallet;
     program = anchor.workspace.SolanaVestingProgram as anchor.Program<SolanaVestingProgram>;
+    await ensureConfig(program, creator.publicKey);
     mint = await createMint(
       connection,
       creator.payer,
@@ -300,6 +334,7 @@ describe("vesting", () => {
     try {
       await program.methods
         .initialize(
+          new anchor.BN(nextId++),
           totalAmount,
           new anchor.BN(Math.floor(Date.now() / 1000)),
           new anchor.BN(Math.floor(Date.now() / 1000) + 10),
@@ -341,6 +376,36 @@ describe("vesting", () => {
     assert.ok(claimable.eq(new anchor.BN(0)), "Should not have claimable amount before cliff");
   });
 
+  it("Reports the vesting status at any timestamp without a signer", async () => {
+    const { vesting } = await setupVesting(20, 0);
+    const account = await program.account.vesting.fetch(vesting);
+
+    const atStart = await program.methods.vestingStatus(account.startTime).accounts({ vesting }).view();
+    expect(atStart.vested.toString()).toBe(totalAmount.muln(20).divn(100).toString());
+    expect(atStart.unvested.toString()).toBe(totalAmount.muln(80).divn(100).toString());
+    expect(atStart.nextUnlockTime.toNumber()).toBe(account.startTime.toNumber() + interval);
+
+    const atEnd = await program.methods.vestingStatus(account.endTime).accounts({ vesting }).view();
+    expect(atEnd.vested.toString()).toBe(totalAmount.toString());
+    expect(atEnd.claimable.toString()).toBe(totalAmount.toString());
+    expect(atEnd.nextUnlockTime).toBeNull();
+  });
+
+  it("Pages through the unlock calendar", async () => {
+    const { vesting } = await setupVesting(20, 0);
+    const account = await program.account.vesting.fetch(vesting);
+
+    const page = await program.methods
+      .unlockCalendar(account.startTime.subn(1), account.endTime, null)
+      .accounts({ vesting })
+      .view();
+    // The cliff plus one unlock every `interval` seconds
+    expect(page.events.length).toBe(1 + 10 / interval);
+    expect(page.events[0].time.toNumber()).toBe(account.startTime.toNumber());
+    expect(page.events[page.events.length - 1].vested.toString()).toBe(totalAmount.toString());
+    expect(page.nextFrom).toBeNull();
+  });
+
   it("Fails if claim is signed by anyone but the beneficiary", async () => {
     const { beneficiary, beneficiaryTokenAccount, vesting, vault } = await setupVesting(20, 0);
 
@@ -435,7 +500,520 @@ describe("vesting", () => {
     }
     assert.ok(failed, "Expected a second revoke to error with AlreadyRevoked");
   });
+
+  it("Blocks claims and revokes while the program is paused", async () => {
+    const { beneficiary, vesting } = await setupVesting(20, 0);
+    await program.methods.setPaused(true).rpc();
+
+    let threw = false;
+    try {
+      const programWithBeneficiary = await newPayerProgram(airdropTokens, beneficiary);
+      await programWithBeneficiary.methods
+        .claim()
+        .accounts({ vesting, mint, tokenProgram: TOKENID } as any)
+        .rpc();
+    } catch (e) {
+      threw = true;
+    }
+
+    let revokeThrew = false;
+    try {
+      await program.methods
+        .revoke()
+        .accounts({ vesting, recipientAccount: creatorTokenAccount, mint, tokenProgram: TOKENID })
+        .rpc();
+    } catch (e) {
+      revokeThrew = true;
+    }
+    await program.methods.setPaused(false).rpc();
+
+    assert.ok(threw, "Expected `claim` to fail with ProgramPaused");
+    assert.ok(revokeThrew, "Expected `revoke` to fail with ProgramPaused");
+  });
+
+  it("Only vests allowlisted mints while the allowlist is enabled", async () => {
+    await program.methods.setMintAllowlist(true).rpc();
+
+    let threw = false;
+    try {
+      await setupVesting(0, 0);
+    } catch (e) {
+      threw = true;
+    }
+    assert.ok(threw, "Expected `initialize` to fail with MintNotAllowed");
+
+    await program.methods.addAllowedMint().accounts({ mint }).rpc();
+    await setupVesting(0, 0);
+
+    await program.methods.setMintAllowlist(false).rpc();
+  });
+
+  it("Charges the creation fee on top of the vested amount unless waived", async () => {
+    const treasury = anchor.web3.Keypair.generate();
+    await airdropTokens(treasury.publicKey);
+    const treasuryTokenAccount = await createAssociatedTokenAccount(
+      connection,
+      creator.payer,
+      mint,
+      treasury.publicKey,
+      {},
+      TOKENID
+    );
+    const feeLamports = new anchor.BN(1_000_000);
+    await program.methods.setFees(feeLamports, 100, treasury.publicKey).rpc();
+
+    const createWithFee = async () => {
+      const beneficiary = anchor.web3.Keypair.generate();
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .initialize(
+          new anchor.BN(nextId++),
+          totalAmount,
+          new anchor.BN(now),
+          new anchor.BN(now + 10),
+          0,
+          null,
+          "With fee",
+          false
+        )
+        .accounts({
+          sourceTokenAccount: creatorTokenAccount,
+          beneficiary: beneficiary.publicKey,
+          mint,
+          tokenProgram: TOKENID,
+          treasury: treasury.publicKey,
+          treasuryTokenAccount,
+        } as any)
+        .rpc();
+    };
+
+    try {
+      const lamportsBefore = await connection.getBalance(treasury.publicKey);
+      await createWithFee();
+      expect(await connection.getBalance(treasury.publicKey)).toBe(lamportsBefore + feeLamports.toNumber());
+      const feeAccount = await getAccount(connection, treasuryTokenAccount, undefined, TOKENID);
+      // 1% of the vested amount, the vault still receives the full amount
+      expect(feeAccount.amount.toString()).toBe(totalAmount.divn(100).toString());
+
+      await program.methods.addFeeWaiver(creator.publicKey).rpc();
+      await createWithFee();
+      expect(await connection.getBalance(treasury.publicKey)).toBe(lamportsBefore + feeLamports.toNumber());
+      await program.methods.removeFeeWaiver().accounts({ feeWaiver: feeWaiverAddress(creator.publicKey) } as any).rpc();
+    } finally {
+      await program.methods.setFees(new anchor.BN(0), 0, creator.publicKey).rpc();
+    }
+  });
+
+  it("Transfers the admin only once the proposed admin accepts", async () => {
+    const config = configAddress(program.programId);
+    const newAdmin = anchor.web3.Keypair.generate();
+    const programWithNewAdmin = await newPayerProgram(airdropTokens, newAdmin);
+    await program.methods.proposeAdmin(newAdmin.publicKey).rpc();
+
+    let state = await program.account.config.fetch(config);
+    expect(state.admin.toBase58()).toBe(creator.publicKey.toBase58());
+    expect(state.pendingAdmin.toBase58()).toBe(newAdmin.publicKey.toBase58());
+
+    let threw = false;
+    try {
+      await program.methods.acceptAdmin().accounts({ pendingAdmin: creator.publicKey } as any).rpc();
+    } catch (e) {
+      threw = true;
+    }
+    assert.ok(threw, "Expected `accept_admin` to fail with NotPendingAdmin");
+
+    await programWithNewAdmin.methods.acceptAdmin().rpc();
+    state = await program.account.config.fetch(config);
+    expect(state.admin.toBase58()).toBe(newAdmin.publicKey.toBase58());
+    expect(state.pendingAdmin).toBeNull();
+
+    // Hand it back for the other tests
+    await programWithNewAdmin.methods.proposeAdmin(creator.publicKey).rpc();
+    await program.methods.acceptAdmin().rpc();
+  });
+
+  it("Lets different creators use the same ID for one beneficiary and mint", async () => {
+    const { beneficiary } = await setupVesting(0, 0);
+    const otherCreator = anchor.web3.Keypair.generate();
+    await airdropTokens(otherCreator.publicKey);
+    const otherTokenAccount = await createAssociatedTokenAccount(
+      connection,
+      creator.payer,
+      mint,
+      otherCreator.publicKey,
+      {},
+      TOKENID
+    );
+    await mintTo(connection, creator.payer, mint, otherTokenAccount, creator.publicKey, 1000, undefined, undefined, TOKENID);
+
+    // Same ID, beneficiary and mint as the schedule above, different creator
+    const id = new anchor.BN(nextId - 1);
+    const [vesting] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("vesting"),
+        otherCreator.publicKey.toBuffer(),
+        beneficiary.publicKey.toBuffer(),
+        mint.toBuffer(),
+        id.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const now = Math.floor(Date.now() / 1000);
+    const otherProgram = await newPayerProgram(airdropTokens, otherCreator);
+    await otherProgram.methods
+      .initialize(id, new anchor.BN(1000), new anchor.BN(now), new anchor.BN(now + 10), 0, null, "Same name", false)
+      .accounts({
+        sourceTokenAccount: otherTokenAccount,
+        beneficiary: beneficiary.publicKey,
+        mint,
+        tokenProgram: TOKENID,
+      })
+      .rpc();
+
+    const vestingAcc = await program.account.vesting.fetch(vesting);
+    expect(vestingAcc.creator.toBase58()).toBe(otherCreator.publicKey.toBase58());
+    expect(vestingAcc.id.toNumber()).toBe(id.toNumber());
+  });
+
+  it("Pauses and resumes vesting, extending the end time", async () => {
+    const { beneficiary, vesting } = await setupVesting(0, 0);
+
+    await program.methods.pause().accounts({ vesting }).rpc();
+    let vestingAcc = await program.account.vesting.fetch(vesting);
+    const pausedAt = vestingAcc.pausedAt.toNumber();
+    expect(pausedAt).toBeGreaterThan(0);
+
+    // Nothing vests while paused
+    const claimableAtPause = await estimateClaimable(vesting, beneficiary);
+    await new Promise((res) => setTimeout(res, 3000));
+    const claimableLater = await estimateClaimable(vesting, beneficiary);
+    expect(claimableLater.toString()).toBe(claimableAtPause.toString());
+
+    let failed = false;
+    try {
+      await program.methods.pause().accounts({ vesting }).rpc();
+    } catch (e) {
+      failed = true;
+    }
+    assert.ok(failed, "Expected a second pause to error with AlreadyPaused");
+
+    await program.methods.resume().accounts({ vesting }).rpc();
+    vestingAcc = await program.account.vesting.fetch(vesting);
+    expect(vestingAcc.pausedAt.toNumber()).toBe(0);
+    expect(vestingAcc.pausedDuration.toNumber()).toBeGreaterThan(0);
+  });
+
+  it("Amends the schedule when signed by creator and beneficiary", async () => {
+    const { beneficiary, vesting } = await setupVesting(20, 0);
+    const before = await program.account.vesting.fetch(vesting);
+
+    await program.methods
+      .amendSchedule(before.endTime.sub(new anchor.BN(5)), 50, new anchor.BN(1), false)
+      .accounts({ vesting, beneficiary: beneficiary.publicKey })
+      .signers([beneficiary])
+      .rpc();
+
+    const after = await program.account.vesting.fetch(vesting);
+    expect(after.endTime.toNumber()).toBe(before.endTime.toNumber() - 5);
+    expect(after.cliffPercentage).toBe(50);
+    expect(after.paymentInterval.toNumber()).toBe(1);
+    expect(after.revocable).toBe(false);
+  });
+
+  it("Fails to amend the schedule if it reduces the vested amount", async () => {
+    const { beneficiary, vesting } = await setupVesting(50, 0);
+    const before = await program.account.vesting.fetch(vesting);
+
+    let threw = false;
+    try {
+      await program.methods
+        .amendSchedule(before.endTime, 0, new anchor.BN(interval), true)
+        .accounts({ vesting, beneficiary: beneficiary.publicKey })
+        .signers([beneficiary])
+        .rpc();
+    } catch (e) {
+      threw = true;
+    }
+    assert.ok(threw, "Expected `amendSchedule` to fail with VestedAmountReduced");
+  });
+
+  it("Fully accelerates vesting as creator", async () => {
+    const { beneficiary, beneficiaryTokenAccount, vesting } = await setupVesting(0, 60);
+
+    await program.methods.accelerate({ full: {} }).accounts({ vesting }).rpc();
+    const claimable = await estimateClaimable(vesting, beneficiary);
+    expect(claimable.toString()).toBe(totalAmount.toString());
+
+    const programWithBeneficiary = await newPayerProgram(airdropTokens, beneficiary);
+    await programWithBeneficiary.methods
+      .claim()
+      .accounts({
+        vesting,
+        mint,
+        tokenProgram: TOKENID,
+      } as any)
+      .rpc();
+    const account = await getAccount(connection, beneficiaryTokenAccount, undefined, TOKENID);
+    expect(account.amount.toString()).toBe(totalAmount.toString());
+  });
+
+  it("Fails to accelerate by moving the end time later", async () => {
+    const { vesting } = await setupVesting(0, 0);
+    const before = await program.account.vesting.fetch(vesting);
+
+    let threw = false;
+    try {
+      await program.methods
+        .accelerate({ endTime: { endTime: before.endTime.add(new anchor.BN(100)) } })
+        .accounts({ vesting })
+        .rpc();
+    } catch (e) {
+      threw = true;
+    }
+    assert.ok(threw, "Expected `accelerate` to fail with InvalidAcceleration");
+  });
+
+  it("Lists schedules in creator and beneficiary indexes until closed", async () => {
+    const beneficiary = anchor.web3.Keypair.generate();
+    await airdropTokens(beneficiary.publicKey);
+    await createAssociatedTokenAccount(connection, creator.payer, mint, beneficiary.publicKey, {}, TOKENID);
+    const page = Math.floor(Math.random() * 1_000_000);
+    const creatorIndex = scheduleIndexAddress(0, creator.publicKey, page);
+    const beneficiaryIndex = scheduleIndexAddress(1, beneficiary.publicKey, 0);
+    await program.methods.createScheduleIndex({ creator: {} }, page).rpc();
+    // The beneficiary opts in to being listed by creating its own page
+    await program.methods
+      .createScheduleIndex({ beneficiary: {} }, 0)
+      .accounts({ owner: beneficiary.publicKey })
+      .signers([beneficiary])
+      .rpc();
+
+    const id = new anchor.BN(nextId++);
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .initialize(id, totalAmount, new anchor.BN(now), new anchor.BN(now + 60), 0, new anchor.BN(interval), "Indexed", true)
+      .accounts({
+        sourceTokenAccount: creatorTokenAccount,
+        beneficiary: beneficiary.publicKey,
+        mint,
+        tokenProgram: TOKENID,
+        creatorIndex,
+        beneficiaryIndex,
+      } as any)
+      .rpc();
+    const [vesting] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("vesting"),
+        creator.publicKey.toBuffer(),
+        beneficiary.publicKey.toBuffer(),
+        mint.toBuffer(),
+        id.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    let index = await program.account.scheduleIndex.fetch(creatorIndex);
+    expect(index.schedules.map((s) => s.toBase58())).toEqual([vesting.toBase58()]);
+    index = await program.account.scheduleIndex.fetch(beneficiaryIndex);
+    expect(index.schedules.map((s) => s.toBase58())).toEqual([vesting.toBase58()]);
+
+    let threw = false;
+    try {
+      await program.methods.close().accounts({ vesting, tokenProgram: TOKENID } as any).rpc();
+    } catch (e) {
+      threw = true;
+    }
+    assert.ok(threw, "Expected `close` to fail with VestingNotFinished");
+
+    await program.methods
+      .revoke()
+      .accounts({ vesting, recipientAccount: creatorTokenAccount, mint, tokenProgram: TOKENID })
+      .rpc();
+    await program.methods
+      .close()
+      .accounts({ vesting, creatorIndex, beneficiaryIndex, tokenProgram: TOKENID } as any)
+      .rpc();
+
+    expect(await connection.getAccountInfo(vesting)).toBeNull();
+    index = await program.account.scheduleIndex.fetch(creatorIndex);
+    expect(index.schedules).toHaveLength(0);
+    index = await program.account.scheduleIndex.fetch(beneficiaryIndex);
+    expect(index.schedules).toHaveLength(0);
+
+    await program.methods
+      .closeScheduleIndex()
+      .accounts({ scheduleIndex: beneficiaryIndex, owner: beneficiary.publicKey } as any)
+      .signers([beneficiary])
+      .rpc();
+    expect(await connection.getAccountInfo(beneficiaryIndex)).toBeNull();
+  });
+
+  it("Prunes schedules closed without their index pages", async () => {
+    const beneficiary = anchor.web3.Keypair.generate();
+    const page = Math.floor(Math.random() * 1_000_000);
+    const creatorIndex = scheduleIndexAddress(0, creator.publicKey, page);
+    await program.methods.createScheduleIndex({ creator: {} }, page).rpc();
+
+    const id = new anchor.BN(nextId++);
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .initialize(id, totalAmount, new anchor.BN(now), new anchor.BN(now + 60), 0, new anchor.BN(interval), "Pruned", true)
+      .accounts({
+        sourceTokenAccount: creatorTokenAccount,
+        beneficiary: beneficiary.publicKey,
+        mint,
+        tokenProgram: TOKENID,
+        creatorIndex,
+      } as any)
+      .rpc();
+    const [vesting] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("vesting"),
+        creator.publicKey.toBuffer(),
+        beneficiary.publicKey.toBuffer(),
+        mint.toBuffer(),
+        id.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    let threw = false;
+    try {
+      await program.methods.pruneScheduleIndex().accounts({ scheduleIndex: creatorIndex, owner: creator.publicKey, vesting } as any).rpc();
+    } catch (e) {
+      threw = true;
+    }
+    assert.ok(threw, "Expected `prune_schedule_index` to fail with ScheduleStillOpen");
+
+    await program.methods
+      .revoke()
+      .accounts({ vesting, recipientAccount: creatorTokenAccount, mint, tokenProgram: TOKENID })
+      .rpc();
+    await program.methods.close().accounts({ vesting, tokenProgram: TOKENID } as any).rpc();
+    await program.methods.pruneScheduleIndex().accounts({ scheduleIndex: creatorIndex, owner: creator.publicKey, vesting } as any).rpc();
+
+    const index = await program.account.scheduleIndex.fetch(creatorIndex);
+    expect(index.schedules).toHaveLength(0);
+  });
+
+  it("Lets the owner of a page remove schedules others listed in it", async () => {
+    const beneficiary = anchor.web3.Keypair.generate();
+    await airdropTokens(beneficiary.publicKey);
+    const beneficiaryIndex = scheduleIndexAddress(1, beneficiary.publicKey, 0);
+    await program.methods
+      .createScheduleIndex({ beneficiary: {} }, 0)
+      .accounts({ owner: beneficiary.publicKey })
+      .signers([beneficiary])
+      .rpc();
+    const emptySize = (await connection.getAccountInfo(beneficiaryIndex))!.data.length;
+
+    // A creator lists a schedule in the beneficiary's page and pays for the entry
+    const id = new anchor.BN(nextId++);
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .initialize(id, new anchor.BN(0), new anchor.BN(now), new anchor.BN(now + 60), 0, null, "Unwanted", true)
+      .accounts({
+        sourceTokenAccount: creatorTokenAccount,
+        beneficiary: beneficiary.publicKey,
+        mint,
+        tokenProgram: TOKENID,
+        beneficiaryIndex,
+      } as any)
+      .rpc();
+    const [vesting] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("vesting"),
+        creator.publicKey.toBuffer(),
+        beneficiary.publicKey.toBuffer(),
+        mint.toBuffer(),
+        id.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    expect((await connection.getAccountInfo(beneficiaryIndex))!.data.length).toBe(emptySize + 32);
+
+    const balance = await connection.getBalance(beneficiary.publicKey);
+    await program.methods
+      .removeScheduleIndexEntries([vesting])
+      .accounts({ scheduleIndex: beneficiaryIndex, owner: beneficiary.publicKey } as any)
+      .signers([beneficiary])
+      .rpc();
+
+    const index = await program.account.scheduleIndex.fetch(beneficiaryIndex);
+    expect(index.schedules).toHaveLength(0);
+    expect((await connection.getAccountInfo(beneficiaryIndex))!.data.length).toBe(emptySize);
+    // The entry's rent goes to the owner, less the transaction fee
+    expect(await connection.getBalance(beneficiary.publicKey)).toBeGreaterThan(balance - 5000);
+    // The schedule itself is untouched
+    expect(await connection.getAccountInfo(vesting)).not.toBeNull();
+  });
+
+  (EVENT_CPI ? it : it.skip)("Emits self-contained events through self-CPI", async () => {
+    const { beneficiary, vesting, initSignature } = await setupVesting(0, 60);
+
+    const [initialized] = await cpiEvents(initSignature);
+    expect(initialized.name).toBe("vestingInitialized");
+    expect(initialized.data.schemaVersion).toBe(1);
+    expect(initialized.data.vesting.toBase58()).toBe(vesting.toBase58());
+    expect(initialized.data.mint.toBase58()).toBe(mint.toBase58());
+    expect(initialized.data.totalAmount.toString()).toBe(totalAmount.toString());
+    expect(initialized.data.paymentInterval.toNumber()).toBe(interval);
+    expect(initialized.data.revocable).toBe(true);
+
+    await program.methods.accelerate({ bonus: { amount: new anchor.BN(1_000) } }).accounts({ vesting }).rpc();
+    const programWithBeneficiary = await newPayerProgram(airdropTokens, beneficiary);
+    const claimSignature = await programWithBeneficiary.methods
+      .claim()
+      .accounts({ vesting, mint, tokenProgram: TOKENID } as any)
+      .rpc({ commitment: "confirmed" });
+    const [claimed] = await cpiEvents(claimSignature);
+    expect(claimed.name).toBe("vestingClaimed");
+    expect(claimed.data.beneficiary.toBase58()).toBe(beneficiary.publicKey.toBase58());
+    expect(claimed.data.amount.toString()).toBe("1000");
+    expect(claimed.data.claimedAmount.toString()).toBe("1000");
+
+    const revokeSignature = await program.methods
+      .revoke()
+      .accounts({ vesting, recipientAccount: creatorTokenAccount, mint, tokenProgram: TOKENID })
+      .rpc({ commitment: "confirmed" });
+    const [revoked] = await cpiEvents(revokeSignature);
+    expect(revoked.name).toBe("revokedEvent");
+    expect(revoked.data.creator.toBase58()).toBe(creator.publicKey.toBase58());
+    expect(revoked.data.vested.toString()).toBe("1000");
+    expect(revoked.data.claimedAmount.toString()).toBe("1000");
+    expect(revoked.data.unvested.toString()).toBe(totalAmount.subn(1000).toString());
+    expect(revoked.data.returned.toString()).toBe(totalAmount.subn(1000).toString());
+  });
+
+  it("Fails to pause if not signed by the creator", async () => {
+    const { beneficiary, vesting } = await setupVesting(0, 0);
+
+    let threw = false;
+    try {
+      const programWithBeneficiary = await newPayerProgram(airdropTokens, beneficiary);
+      await programWithBeneficiary.methods
+        .pause()
+        .accounts({ vesting, creator: beneficiary.publicKey } as any)
+        .rpc();
+    } catch (e) {
+      threw = true;
+    }
+    assert.ok(threw, "Expected `pause` to fail when the transaction is not signed by the creator");
+  });
 });
+function scheduleIndexAddress(kind: number, owner: PublicKey, page: number) {
+  const pageBytes = Buffer.alloc(4);
+  pageBytes.writeUInt32LE(page);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("schedule_index"), Buffer.from([kind]), owner.toBuffer(), pageBytes],
+    program.programId
+  )[0];
+}
+
+function feeWaiverAddress(creatorKey: PublicKey) {
+  return PublicKey.findProgramAddressSync([Buffer.from("fee_waiver"), creatorKey.toBuffer()], program.programId)[0];
+}
+
 async function newPayerProgram(
   airdropTokens: (to: PublicKey) => Promise<void>,
   beneficiary: anchor.web3.Keypair